@@ -1,8 +1,9 @@
 use crate::context::token_manager::TokenInfo as CliTokenInfo;
 use crate::context::token_manager::TokenManager;
-use cloudreve_api::{CloudreveAPI, LoginResponse, Result};
+use crate::utils::credentials::{self, SecretSource};
+use cloudreve_api::api::v4::models::TwoFactorLoginRequest;
+use cloudreve_api::{CloudreveAPI, LoginResponse, Result, UnifiedClient};
 use log::{debug, info};
-use rpassword::read_password;
 use std::io::{self, Write};
 
 pub async fn handle_auth(
@@ -10,7 +11,8 @@ pub async fn handle_auth(
     token_manager: &TokenManager,
     email: Option<String>,
     url: Option<String>,
-    password: Option<String>,
+    password: SecretSource<'_>,
+    otp: Option<String>,
 ) -> Result<()> {
     info!("Authenticating...");
 
@@ -36,24 +38,25 @@ pub async fn handle_auth(
         email_input.trim().to_string()
     };
 
-    // If no password provided, we need to ask for it securely
-    let password = if let Some(pwd) = password {
-        pwd
-    } else {
-        print!("Enter your password: ");
-        io::stdout().flush()?;
-        let password_input = read_password().unwrap_or_default();
-        password_input.trim().to_string()
-    };
-
-    // Use the unified login method
-    let login_response = api.login(&email, &password).await?;
+    // Resolve password from flag/stdin/file/env, prompting securely on a TTY
+    let password = credentials::require_secret(password, "Enter your password: ")?;
 
     // Get API version
     let api_version = api.api_version().to_string();
 
+    // Use the unified login method, falling back to the 2FA flow when challenged
+    let login_response = match api.login(&email, &password).await {
+        Ok(response) => response,
+        Err(cloudreve_api::Error::TwoFactorRequired(session_id)) => {
+            info!("Two-factor authentication is enabled for this account");
+            let cli_token_info = two_factor_login(api, &url, &email, &session_id, otp).await?;
+            return finish_login(token_manager, cli_token_info);
+        }
+        Err(e) => return Err(e),
+    };
+
     // Extract user info and token info from response
-    let (user_id, nickname, cli_token_info) = match &login_response {
+    let cli_token_info = match &login_response {
         LoginResponse::V3(r) => {
             // V3 uses session cookie - get it from the client
             let session_cookie = api.get_session_cookie().unwrap_or_else(|| {
@@ -65,54 +68,93 @@ pub async fn handle_auth(
                 debug!("Warning: V3 login returned empty session cookie");
             }
 
-            let api_v = api_version.clone();
-            (
-                r.user.id.clone(),
-                r.user.nickname.clone(),
-                CliTokenInfo {
-                    user_id: r.user.id.clone(),
-                    email: email.clone(),
-                    nickname: r.user.nickname.clone(),
-                    access_token: session_cookie, // Save the session cookie
-                    refresh_token: String::new(),
-                    access_expires: String::new(),
-                    refresh_expires: String::new(),
-                    url: url.clone(),
-                    api_version: api_v,
-                },
-            )
+            CliTokenInfo {
+                user_id: r.user.id.clone(),
+                email: email.clone(),
+                nickname: r.user.nickname.clone(),
+                access_token: session_cookie, // Save the session cookie
+                refresh_token: String::new(),
+                access_expires: String::new(),
+                refresh_expires: String::new(),
+                url: url.clone(),
+                api_version: api_version.clone(),
+            }
         }
         LoginResponse::V4(r) => {
             // V4 has proper JWT tokens
-            let api_v = api_version.clone();
-            (
-                r.user.id.clone(),
-                r.user.nickname.clone(),
-                CliTokenInfo {
-                    user_id: r.user.id.clone(),
-                    email: email.clone(),
-                    nickname: r.user.nickname.clone(),
-                    access_token: r.token.access_token.clone(),
-                    refresh_token: r.token.refresh_token.clone(),
-                    access_expires: r.token.access_expires.clone(),
-                    refresh_expires: r.token.refresh_expires.clone(),
-                    url: url.clone(),
-                    api_version: api_v,
-                },
-            )
+            CliTokenInfo {
+                user_id: r.user.id.clone(),
+                email: email.clone(),
+                nickname: r.user.nickname.clone(),
+                access_token: r.token.access_token.clone(),
+                refresh_token: r.token.refresh_token.clone(),
+                access_expires: r.token.access_expires.clone(),
+                refresh_expires: r.token.refresh_expires.clone(),
+                url: url.clone(),
+                api_version: api_version.clone(),
+            }
         }
     };
 
-    info!("Authentication successful!");
-    info!("User ID: {}", user_id);
-    info!("User Nickname: {}", nickname);
-    info!("API Version: {}", api_version);
-
     debug!("Login response: {:?}", login_response);
 
+    finish_login(token_manager, cli_token_info)
+}
+
+/// Reports the login result and caches the token
+fn finish_login(token_manager: &TokenManager, cli_token_info: CliTokenInfo) -> Result<()> {
+    info!("Authentication successful!");
+    info!("User ID: {}", cli_token_info.user_id);
+    info!("User Nickname: {}", cli_token_info.nickname);
+    info!("API Version: {}", cli_token_info.api_version);
+
     // Save token to cache
     token_manager.save_token(&cli_token_info)?;
-    info!("Token saved to cache for user: {}({})", nickname, email);
+    info!(
+        "Token saved to cache for user: {}({})",
+        cli_token_info.nickname, cli_token_info.email
+    );
 
     Ok(())
 }
+
+/// Completes a V4 login that was challenged for a TOTP code
+///
+/// The session ID returned with the challenge is passed to the second step,
+/// so the password is only sent once.
+async fn two_factor_login(
+    api: &mut CloudreveAPI,
+    url: &str,
+    email: &str,
+    session_id: &str,
+    otp: Option<String>,
+) -> Result<CliTokenInfo> {
+    let UnifiedClient::V4(client) = api.inner() else {
+        return Err(cloudreve_api::Error::InvalidResponse(
+            "Two-factor login is only supported for V4 API".to_string(),
+        ));
+    };
+
+    let otp = credentials::resolve_otp(otp)?;
+    let login_data = client
+        .finish_2fa_login(&TwoFactorLoginRequest {
+            otp: &otp,
+            session_id,
+        })
+        .await?;
+
+    let cli_token_info = CliTokenInfo {
+        user_id: login_data.user.id.clone(),
+        email: email.to_string(),
+        nickname: login_data.user.nickname.clone(),
+        access_token: login_data.token.access_token.clone(),
+        refresh_token: login_data.token.refresh_token.clone(),
+        access_expires: login_data.token.access_expires.clone(),
+        refresh_expires: login_data.token.refresh_expires.clone(),
+        url: url.to_string(),
+        api_version: api.api_version().to_string(),
+    };
+
+    api.set_token(&cli_token_info.access_token)?;
+    Ok(cli_token_info)
+}
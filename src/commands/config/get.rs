@@ -0,0 +1,22 @@
+use super::config_error;
+use crate::config::Config;
+use cloudreve_api::Result;
+
+pub fn handle_get(key: String) -> Result<()> {
+    let config = Config::load().map_err(config_error)?;
+
+    if !Config::keys().contains(&key.as_str()) {
+        return Err(config_error(format!(
+            "Unknown config key: {}. Available keys: {}",
+            key,
+            Config::keys().join(", ")
+        )));
+    }
+
+    // Print the bare value so it can be used in scripts
+    if let Some(value) = config.get(&key) {
+        println!("{}", value);
+    }
+
+    Ok(())
+}
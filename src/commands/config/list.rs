@@ -0,0 +1,22 @@
+use super::config_error;
+use crate::config::Config;
+use cloudreve_api::Result;
+
+pub fn handle_list() -> Result<()> {
+    let layers = Config::load_layers().map_err(config_error)?;
+
+    for key in Config::keys() {
+        // The last layer that sets a key wins
+        let effective = layers
+            .iter()
+            .rev()
+            .find_map(|(source, layer)| layer.get(key).map(|value| (source, value)));
+
+        match effective {
+            Some((source, value)) => println!("{} = {}  ({})", key, value, source),
+            None => println!("{} = (unset)", key),
+        }
+    }
+
+    Ok(())
+}
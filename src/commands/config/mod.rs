@@ -0,0 +1,85 @@
+// CLI configuration commands
+
+pub mod get;
+pub mod list;
+pub mod path;
+pub mod set;
+pub mod unset;
+
+use crate::config::{Config, LOCAL_CONFIG_FILE};
+use cloudreve_api::Result;
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Subcommand)]
+pub enum ConfigCommands {
+    /// Get the effective value of a configuration key
+    Get {
+        /// Configuration key
+        key: String,
+    },
+
+    /// Set a configuration key
+    Set {
+        /// Configuration key
+        key: String,
+
+        /// Value to store
+        value: String,
+
+        /// Write to the project-local .cloudreve.toml instead of the global file
+        #[clap(long)]
+        local: bool,
+    },
+
+    /// Remove a configuration key
+    Unset {
+        /// Configuration key
+        key: String,
+
+        /// Remove from the project-local .cloudreve.toml instead of the global file
+        #[clap(long)]
+        local: bool,
+    },
+
+    /// List effective configuration values and where they come from
+    List,
+
+    /// Show configuration file locations
+    Path,
+}
+
+pub fn handle_config_command(command: ConfigCommands) -> Result<()> {
+    match command {
+        ConfigCommands::Get { key } => get::handle_get(key),
+        ConfigCommands::Set { key, value, local } => set::handle_set(key, value, local),
+        ConfigCommands::Unset { key, local } => unset::handle_unset(key, local),
+        ConfigCommands::List => list::handle_list(),
+        ConfigCommands::Path => path::handle_path(),
+    }
+}
+
+/// Resolves the file a `set`/`unset` should modify
+fn target_file(local: bool) -> Result<PathBuf> {
+    if local {
+        // Prefer an existing project file, otherwise create one in the current directory
+        match Config::local_config_path() {
+            Some(path) => Ok(path),
+            None => Ok(std::env::current_dir()?.join(LOCAL_CONFIG_FILE)),
+        }
+    } else {
+        Config::config_file_path().map_err(config_error)
+    }
+}
+
+/// Loads a single config file for editing, missing files start empty
+fn load_for_edit(path: &Path) -> Result<Config> {
+    if path.exists() {
+        Config::load_file(path).map_err(config_error)
+    } else {
+        Ok(Config::empty())
+    }
+}
+
+fn config_error(e: impl std::fmt::Display) -> cloudreve_api::Error {
+    cloudreve_api::Error::InvalidResponse(format!("Configuration error: {}", e))
+}
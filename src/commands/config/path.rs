@@ -0,0 +1,19 @@
+use super::config_error;
+use crate::config::{Config, LOCAL_CONFIG_FILE};
+use cloudreve_api::Result;
+
+pub fn handle_path() -> Result<()> {
+    let global = Config::config_file_path().map_err(config_error)?;
+    println!(
+        "global: {}{}",
+        global.display(),
+        if global.exists() { "" } else { " (not found)" }
+    );
+
+    match Config::local_config_path() {
+        Some(local) => println!("local:  {}", local.display()),
+        None => println!("local:  (no {} found)", LOCAL_CONFIG_FILE),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use super::{config_error, load_for_edit, target_file};
+use crate::config::{CONNECTION_KEYS, LOCAL_CONFIG_FILE};
+use cloudreve_api::Result;
+use log::info;
+
+pub fn handle_set(key: String, value: String, local: bool) -> Result<()> {
+    if local && CONNECTION_KEYS.contains(&key.as_str()) {
+        return Err(config_error(format!(
+            "{} cannot be set in {}, use the global config instead",
+            key, LOCAL_CONFIG_FILE
+        )));
+    }
+
+    let path = target_file(local)?;
+    let mut config = load_for_edit(&path)?;
+
+    config.set(&key, &value).map_err(config_error)?;
+    config.save_to(&path).map_err(config_error)?;
+
+    info!("Set {} = {} in {}", key, value, path.display());
+    Ok(())
+}
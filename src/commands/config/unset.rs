@@ -0,0 +1,18 @@
+use super::{config_error, load_for_edit, target_file};
+use cloudreve_api::Result;
+use log::info;
+
+pub fn handle_unset(key: String, local: bool) -> Result<()> {
+    let path = target_file(local)?;
+    if !path.exists() {
+        info!("Nothing to unset, {} does not exist", path.display());
+        return Ok(());
+    }
+
+    let mut config = load_for_edit(&path)?;
+    config.unset(&key).map_err(config_error)?;
+    config.save_to(&path).map_err(config_error)?;
+
+    info!("Removed {} from {}", key, path.display());
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use cloudreve_api::{CloudreveAPI, Result};
+use super::credential_url;
+use crate::utils::qr::QrOptions;
+use cloudreve_api::api::v4::models::CreateDavAccountRequest;
+use cloudreve_api::{CloudreveAPI, Error, Result, UnifiedClient};
 use log::info;
 
 pub async fn handle_create(
@@ -7,14 +10,42 @@ pub async fn handle_create(
     name: String,
     readonly: bool,
     proxy: bool,
+    qr: QrOptions,
 ) -> Result<()> {
     info!("Creating WebDAV account '{}'...", name);
 
-    api.create_dav_account(&uri, &name, readonly, proxy).await?;
+    // The unified call drops the created account, which holds the password
+    let account = match api.inner() {
+        UnifiedClient::V4(client) => {
+            client
+                .create_dav_account(&CreateDavAccountRequest {
+                    uri: uri.clone(),
+                    name: name.clone(),
+                    readonly: Some(readonly),
+                    proxy: Some(proxy),
+                    disable_sys_files: None,
+                })
+                .await?
+        }
+        // The V3 call does not return the password, so there is nothing to encode
+        UnifiedClient::V3(_) if qr.is_enabled() => {
+            return Err(Error::InvalidResponse(
+                "--qr and --qr-file not yet supported for V3 API".to_string(),
+            ));
+        }
+        UnifiedClient::V3(_) => return api.create_dav_account(&uri, &name, readonly, proxy).await,
+    };
 
     info!("WebDAV account created successfully!");
+    info!("  ID:       {}", account.id);
     info!("  Name:     {}", name);
     info!("  URI:      {}", uri);
+    info!("  Password: {}", account.password);
+
+    if qr.is_enabled() {
+        let email = api.get_user_info().await?.email;
+        qr.show(&credential_url(api.base_url(), &email, &account.password)?)?;
+    }
 
     Ok(())
 }
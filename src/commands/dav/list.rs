@@ -1,19 +1,43 @@
+use super::credential_url;
+use crate::utils::qr::QrOptions;
 use cloudreve_api::api::v4::uri::uri_to_path;
-use cloudreve_api::{CloudreveAPI, Result};
+use cloudreve_api::{CloudreveAPI, Error, Result};
 use log::info;
 
-pub async fn handle_list(api: &CloudreveAPI, page_size: u32) -> Result<()> {
+pub async fn handle_list(
+    api: &CloudreveAPI,
+    page_size: u32,
+    ids: Vec<String>,
+    qr: QrOptions,
+) -> Result<()> {
     info!("Listing WebDAV accounts...");
 
-    let response = api.list_dav_accounts(page_size).await?;
+    let mut accounts = api.list_dav_accounts(page_size).await?.accounts;
+    if !ids.is_empty() {
+        accounts.retain(|a| ids.contains(&a.id));
+    }
 
-    if response.accounts.is_empty() {
+    if accounts.is_empty() {
         info!("No WebDAV accounts found.");
         return Ok(());
     }
 
-    info!("Found {} WebDAV account(s):", response.accounts.len());
-    for account in response.accounts {
+    if qr.qr_file.is_some() && accounts.len() != 1 {
+        return Err(Error::InvalidResponse(format!(
+            "--qr-file needs exactly one WebDAV account, {} matched (use --id)",
+            accounts.len()
+        )));
+    }
+
+    // The WebDAV user name in the QR code is the login email
+    let email = if qr.is_enabled() {
+        Some(api.get_user_info().await?.email)
+    } else {
+        None
+    };
+
+    info!("Found {} WebDAV account(s):", accounts.len());
+    for account in accounts {
         info!("");
         info!("  ID:          {}", account.id);
         info!("  Name:        {}", account.name);
@@ -31,6 +55,9 @@ pub async fn handle_list(api: &CloudreveAPI, page_size: u32) -> Result<()> {
             info!("  Password:    {}", password);
         }
         info!("  Created:     {}", account.created_at);
+        if let (Some(email), Some(password)) = (&email, &account.password) {
+            qr.show(&credential_url(api.base_url(), email, password)?)?;
+        }
     }
 
     Ok(())
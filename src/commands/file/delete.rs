@@ -1,14 +1,17 @@
-use cloudreve_api::{CloudreveAPI, Result};
+use cloudreve_api::api::v4::models::DeleteFileRequest;
+use cloudreve_api::{CloudreveAPI, Error, FileListAll, Result, UnifiedClient};
 use log::{error, info};
 use std::io::{self, Write};
 
-use crate::utils::glob;
+use crate::utils::{envelope, glob, remote};
 
 pub async fn handle_delete(
     api: &CloudreveAPI,
     uris: Vec<String>,
     force: bool,
-    _recursive: bool,
+    recursive: bool,
+    permanent: bool,
+    unlink: bool,
 ) -> Result<()> {
     if uris.is_empty() {
         error!("No files specified for deletion");
@@ -19,14 +22,60 @@ pub async fn handle_delete(
     // For delete, we include folders to support /* pattern
     let paths_to_delete = glob::expand_remote_patterns(api, &uris, true).await?;
 
+    delete_paths(api, paths_to_delete, force, recursive, permanent, unlink).await
+}
+
+/// Deletes exact paths, without expanding wildcards in their names
+pub async fn delete_paths(
+    api: &CloudreveAPI,
+    paths_to_delete: Vec<String>,
+    force: bool,
+    recursive: bool,
+    permanent: bool,
+    unlink: bool,
+) -> Result<()> {
+    // Trash and unlink are V4 concepts; V3 always deletes permanently
+    let is_v3 = matches!(api.inner(), UnifiedClient::V3(_));
+    if is_v3 && (permanent || unlink) {
+        return Err(Error::InvalidResponse(
+            "--permanent and --unlink not supported for V3 API, which always deletes permanently"
+                .to_string(),
+        ));
+    }
+
     if paths_to_delete.is_empty() {
         info!("No files to delete");
         return Ok(());
     }
 
+    // Refuse non-empty folders unless --recursive was given
+    if !recursive {
+        let non_empty = find_non_empty_folders(api, &paths_to_delete).await?;
+        if !non_empty.is_empty() {
+            for path in &non_empty {
+                error!("Folder is not empty: {}", path);
+            }
+            return Err(Error::InvalidResponse(format!(
+                "{} folder(s) not empty, use --recursive to delete them with their contents",
+                non_empty.len()
+            )));
+        }
+    }
+
+    let mode = if is_v3 {
+        "permanent (V3 has no trash)"
+    } else if unlink {
+        "unlink (keep shared blobs)"
+    } else if permanent {
+        "permanent (skip trash)"
+    } else {
+        "move to trash"
+    };
+
     // Confirmation
     if !force {
         println!("Delete operation:");
+        println!("  Mode:  {}", mode);
         println!("  Items: {}", paths_to_delete.len());
         for path in &paths_to_delete {
             println!("  - {}", path);
@@ -43,6 +92,33 @@ pub async fn handle_delete(
         }
     }
 
+    if let UnifiedClient::V4(client) = api.inner()
+        && (permanent || unlink)
+    {
+        let uris: Vec<String> = paths_to_delete.iter().map(|p| remote::to_uri(p)).collect();
+        let request = DeleteFileRequest {
+            uris: uris.iter().map(|u| u.as_str()).collect(),
+            unlink: unlink.then_some(true),
+            skip_soft_delete: permanent.then_some(true),
+        };
+
+        if let Err(e) = client
+            .delete_with_body("/file", &request)
+            .await
+            .and_then(envelope::check)
+        {
+            error!("Delete operation failed: {}", e);
+            return Err(e);
+        }
+
+        info!(
+            "Delete complete: {} item(s) removed ({})",
+            paths_to_delete.len(),
+            mode
+        );
+        return Ok(());
+    }
+
     // Convert to slice for batch_delete
     let paths_refs: Vec<&str> = paths_to_delete.iter().map(|s| s.as_str()).collect();
 
@@ -69,3 +145,28 @@ pub async fn handle_delete(
 
     Ok(())
 }
+
+/// Returns the paths that are folders with at least one child
+async fn find_non_empty_folders(api: &CloudreveAPI, paths: &[String]) -> Result<Vec<String>> {
+    let mut non_empty = Vec::new();
+
+    for path in paths {
+        // Missing paths are reported by the delete call itself
+        let Ok(info) = api.get_file_info(path).await else {
+            continue;
+        };
+        if !info.is_folder() {
+            continue;
+        }
+
+        let first_page = match api.list_files(path, None, Some(1)).await? {
+            cloudreve_api::FileList::V3(d) => FileListAll::V3(d),
+            cloudreve_api::FileList::V4(r) => FileListAll::V4(r),
+        };
+        if first_page.items().into_iter().next().is_some() {
+            non_empty.push(path.clone());
+        }
+    }
+
+    Ok(non_empty)
+}
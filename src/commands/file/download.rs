@@ -11,6 +11,7 @@ use crate::utils::glob;
 /// Download a single file
 pub async fn download_single_file(
     api: &CloudreveAPI,
+    http_client: &Client,
     uri: String,
     output: String,
     _expires_in: Option<u32>,
@@ -30,7 +31,7 @@ pub async fn download_single_file(
         uri.split('/').next_back().unwrap_or("downloaded_file")
     };
 
-    let output_path = if output.ends_with('/') || output == "." || output == "./" {
+    let output_path = if output.ends_with('/') || Path::new(&output).is_dir() {
         format!("{}/{}", output.trim_end_matches('/'), file_name)
     } else {
         output
@@ -38,9 +39,18 @@ pub async fn download_single_file(
 
     info!("Saving to: {}", output_path);
 
-    // 3. Download file
-    let http_client = Client::new();
-    let response = http_client.get(&download_url).send().await?;
+    // 3. Download and save to local file
+    let size = save_url(http_client, &download_url, &output_path).await?;
+
+    info!("Saved to: {}", output_path);
+    info!("Size: {}", format_bytes(size as i64));
+
+    Ok(())
+}
+
+/// Fetches a download URL and writes the body to `output_path`
+pub async fn save_url(http_client: &Client, url: &str, output_path: &str) -> Result<usize> {
+    let response = http_client.get(url).send().await?;
 
     if !response.status().is_success() {
         error!("Download failed with status: {}", response.status());
@@ -62,22 +72,19 @@ pub async fn download_single_file(
     );
     pb.set_message("Downloading");
 
-    // 4. Download and save to local file
     let bytes = response.bytes().await?;
-    let mut file = File::create(&output_path)?;
+    let mut file = File::create(output_path)?;
     file.write_all(&bytes)?;
 
     pb.finish_with_message("Download completed!");
 
-    info!("Saved to: {}", output_path);
-    info!("Size: {}", format_bytes(bytes.len() as i64));
-
-    Ok(())
+    Ok(bytes.len())
 }
 
 /// Handle download with support for multiple files and concurrency
 pub async fn handle_download(
     api: &CloudreveAPI,
+    http_client: &Client,
     files: Vec<String>,
     output: String,
     expires_in: Option<u32>,
@@ -93,11 +100,36 @@ pub async fn handle_download(
     // Expand glob patterns for remote files
     let expanded_files = glob::expand_remote_patterns(api, &files, false).await?;
 
+    download_paths(
+        api,
+        http_client,
+        expanded_files,
+        output,
+        expires_in,
+        concurrency,
+    )
+    .await
+}
+
+/// Downloads exact paths, without expanding wildcards in their names
+pub async fn download_paths(
+    api: &CloudreveAPI,
+    http_client: &Client,
+    expanded_files: Vec<String>,
+    output: String,
+    expires_in: Option<u32>,
+    concurrency: usize,
+) -> Result<()> {
     if expanded_files.is_empty() {
         info!("No files matched the specified pattern(s)");
         return Ok(());
     }
 
+    // A configured download directory may not exist yet
+    if expanded_files.len() > 1 || output.ends_with('/') {
+        std::fs::create_dir_all(&output)?;
+    }
+
     info!("Starting download of {} item(s)", expanded_files.len());
 
     // Use concurrency control to download
@@ -105,6 +137,7 @@ pub async fn handle_download(
         .into_iter()
         .map(|uri| {
             let api = api.clone();
+            let http_client = http_client.clone();
             let output = output.clone();
             let file_name = Path::new(&uri)
                 .file_name()
@@ -113,7 +146,7 @@ pub async fn handle_download(
                 .to_string();
 
             (file_name.clone(), async move {
-                download_single_file(&api, uri, output, expires_in).await
+                download_single_file(&api, &http_client, uri, output, expires_in).await
             })
         })
         .collect();
@@ -0,0 +1,83 @@
+use crate::utils::format_bytes;
+use crate::utils::walk;
+use cloudreve_api::{CloudreveAPI, Result};
+use log::info;
+use std::collections::HashMap;
+
+#[derive(Default, Clone, Copy)]
+struct Usage {
+    size: i64,
+    files: usize,
+}
+
+pub async fn handle_du(
+    api: &CloudreveAPI,
+    path: String,
+    max_depth: usize,
+    bytes: bool,
+    concurrency: usize,
+) -> Result<()> {
+    let root = walk::normalize_path(&path);
+    info!("Calculating disk usage of {}...", root);
+
+    let nodes = walk::walk(api, &root, None, concurrency).await?;
+
+    // Every folder starts at zero so empty ones are still reported
+    let mut usage: HashMap<String, Usage> = HashMap::new();
+    usage.insert(root.clone(), Usage::default());
+    let mut depths: HashMap<String, usize> = HashMap::new();
+    depths.insert(root.clone(), 0);
+
+    for node in &nodes {
+        if node.is_folder {
+            usage.entry(node.path.clone()).or_default();
+            depths.insert(node.path.clone(), node.depth);
+            continue;
+        }
+
+        // Add the file to every ancestor up to the root
+        let mut dir = node.parent().to_string();
+        loop {
+            let entry = usage.entry(dir.clone()).or_default();
+            entry.size += node.size;
+            entry.files += 1;
+
+            if dir == root || dir == "/" {
+                break;
+            }
+            dir = match dir.rfind('/') {
+                Some(0) | None => "/".to_string(),
+                Some(pos) => dir[..pos].to_string(),
+            };
+        }
+    }
+
+    let mut rows: Vec<(&String, Usage)> = usage
+        .iter()
+        .filter(|(dir, _)| *dir != &root && depths.get(*dir).is_some_and(|d| *d <= max_depth))
+        .map(|(dir, u)| (dir, *u))
+        .collect();
+    rows.sort_by(|a, b| b.1.size.cmp(&a.1.size).then(a.0.cmp(b.0)));
+
+    let format_size = |size: i64| {
+        if bytes {
+            size.to_string()
+        } else {
+            format_bytes(size)
+        }
+    };
+
+    for (dir, u) in &rows {
+        println!("{:>12}  {:>8} files  {}", format_size(u.size), u.files, dir);
+    }
+
+    let total = usage.get(&root).copied().unwrap_or_default();
+    println!(
+        "{:>12}  {:>8} files  {} (total)",
+        format_size(total.size),
+        total.files,
+        root
+    );
+
+    Ok(())
+}
@@ -0,0 +1,281 @@
+use super::{delete, move_cmd};
+use crate::utils::concurrency::execute_with_concurrency;
+use crate::utils::format_bytes;
+use crate::utils::walk::{self, RemoteNode};
+use cloudreve_api::{CloudreveAPI, Error, Result};
+use log::{error, info, warn};
+use reqwest::Client;
+use sha2::{Digest, Sha256};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+
+/// Scan options
+pub struct DupesOptions {
+    /// Ignore files smaller than this (bytes)
+    pub min_size: i64,
+    /// Which copy to keep: oldest, newest or shortest
+    pub keep: String,
+    /// Treat equal sizes as duplicates without hashing
+    pub size_only: bool,
+    pub concurrency: usize,
+}
+
+/// What to do with the extra copies of each group
+pub enum DupesAction {
+    Report,
+    Delete { force: bool },
+    Move { dest: String },
+}
+
+/// A confirmed duplicate group
+struct DupeGroup {
+    size: i64,
+    /// Sorted so the copy to keep comes first
+    files: Vec<RemoteNode>,
+}
+
+impl DupeGroup {
+    fn wasted(&self) -> i64 {
+        self.size * (self.files.len() as i64 - 1)
+    }
+}
+
+pub async fn handle_dupes(
+    api: &CloudreveAPI,
+    http_client: &Client,
+    path: String,
+    options: DupesOptions,
+    action: DupesAction,
+) -> Result<()> {
+    let DupesOptions {
+        min_size,
+        keep,
+        size_only,
+        concurrency,
+    } = options;
+
+    info!("Scanning {} for duplicate files...", path);
+    let nodes = walk::walk(api, &path, None, concurrency).await?;
+
+    // 1. Group candidates by size
+    let mut by_size: HashMap<i64, Vec<RemoteNode>> = HashMap::new();
+    for node in nodes
+        .into_iter()
+        .filter(|n| !n.is_folder && n.size >= min_size.max(1))
+    {
+        by_size.entry(node.size).or_default().push(node);
+    }
+    by_size.retain(|_, files| files.len() > 1);
+
+    let candidates: usize = by_size.values().map(|f| f.len()).sum();
+    info!(
+        "{} size group(s) with {} candidate file(s)",
+        by_size.len(),
+        candidates
+    );
+
+    // 2. Confirm by content digest
+    let mut groups = if size_only {
+        by_size
+            .into_iter()
+            .map(|(size, files)| DupeGroup { size, files })
+            .collect()
+    } else {
+        confirm_by_digest(api, http_client, by_size, concurrency).await
+    };
+
+    if groups.is_empty() {
+        info!("No duplicates found");
+        return Ok(());
+    }
+
+    // 3. Order each group so the copy to keep is first
+    for group in &mut groups {
+        order_by_keep_rule(group, &keep)?;
+    }
+    groups.sort_by_key(|g| Reverse(g.wasted()));
+
+    let total_wasted: i64 = groups.iter().map(|g| g.wasted()).sum();
+    for (i, group) in groups.iter().enumerate() {
+        info!("");
+        info!(
+            "Group {}: {} copies × {}, wasted {}",
+            i + 1,
+            group.files.len(),
+            format_bytes(group.size),
+            format_bytes(group.wasted())
+        );
+        for (j, file) in group.files.iter().enumerate() {
+            info!("  {} {}", if j == 0 { "keep" } else { "dupe" }, file.path);
+        }
+    }
+    info!("");
+    info!(
+        "{} duplicate group(s), {} wasted",
+        groups.len(),
+        format_bytes(total_wasted)
+    );
+
+    let extras: Vec<&RemoteNode> = groups.iter().flat_map(|g| &g.files[1..]).collect();
+
+    // Scanned names are used as-is so `*` or `?` in them are not re-expanded
+    match action {
+        DupesAction::Report => Ok(()),
+        DupesAction::Delete { force } => {
+            let paths = extras.iter().map(|f| f.path.clone()).collect();
+            delete::delete_paths(api, paths, force, false, false, false).await
+        }
+        DupesAction::Move { dest } => move_extras(api, &path, &extras, &dest).await,
+    }
+}
+
+/// Moves extra copies under `dest`, keeping their folders relative to `root`
+///
+/// Copies usually share a name, so moving them all into one folder would
+/// collide.
+async fn move_extras(
+    api: &CloudreveAPI,
+    root: &str,
+    extras: &[&RemoteNode],
+    dest: &str,
+) -> Result<()> {
+    let root = walk::normalize_path(root);
+    let dest = walk::normalize_path(dest);
+
+    let mut by_target: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for file in extras {
+        let relative = file
+            .parent()
+            .strip_prefix(root.as_str())
+            .unwrap_or_default()
+            .trim_matches('/');
+        let target = if relative.is_empty() {
+            dest.clone()
+        } else {
+            walk::join_path(&dest, relative)
+        };
+        by_target.entry(target).or_default().push(file.path.clone());
+    }
+
+    let mut failed = 0;
+    for (target, paths) in &by_target {
+        let result = match ensure_folder(api, target).await {
+            Ok(()) => move_cmd::move_paths(api, paths, target).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            error!("Failed to move duplicates into {}: {}", target, e);
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        return Err(Error::InvalidResponse(format!(
+            "Failed to move duplicates into {} out of {} folders",
+            failed,
+            by_target.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Creates `path` and any missing parent folders
+async fn ensure_folder(api: &CloudreveAPI, path: &str) -> Result<()> {
+    let mut current = String::new();
+    for name in path.split('/').filter(|s| !s.is_empty()) {
+        current = walk::join_path(&current, name);
+        if api.get_file_info(&current).await.is_err() {
+            api.create_directory(&current).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Splits size groups into groups of identical content
+async fn confirm_by_digest(
+    api: &CloudreveAPI,
+    http_client: &Client,
+    by_size: HashMap<i64, Vec<RemoteNode>>,
+    concurrency: usize,
+) -> Vec<DupeGroup> {
+    let files: HashMap<String, RemoteNode> = by_size
+        .into_values()
+        .flatten()
+        .map(|file| (file.path.clone(), file))
+        .collect();
+
+    info!("Hashing {} file(s)...", files.len());
+    let tasks: Vec<_> = files
+        .keys()
+        .map(|path| {
+            let api = api.clone();
+            let http_client = http_client.clone();
+            let path = path.clone();
+            (path.clone(), async move {
+                digest_remote(&api, &http_client, &path).await
+            })
+        })
+        .collect();
+
+    let mut by_digest: HashMap<(i64, String), Vec<RemoteNode>> = HashMap::new();
+    for (path, result) in execute_with_concurrency(tasks, concurrency).await {
+        match result {
+            Ok(digest) => {
+                let file = files[&path].clone();
+                by_digest.entry((file.size, digest)).or_default().push(file);
+            }
+            Err(e) => warn!("Skipping {}: {}", path, e),
+        }
+    }
+
+    by_digest
+        .into_iter()
+        .filter(|(_, files)| files.len() > 1)
+        .map(|((size, _), files)| DupeGroup { size, files })
+        .collect()
+}
+
+/// Streams a remote file and returns its SHA-256 digest
+async fn digest_remote(api: &CloudreveAPI, http_client: &Client, path: &str) -> Result<String> {
+    let url = api.download_file(path).await?;
+    let mut response = http_client.get(&url).send().await?;
+
+    if !response.status().is_success() {
+        return Err(Error::Api {
+            code: response.status().as_u16() as i32,
+            message: "Download failed".to_string(),
+        });
+    }
+
+    let mut hasher = Sha256::new();
+    while let Some(chunk) = response.chunk().await? {
+        hasher.update(&chunk);
+    }
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// Sorts a group by the keep rule: oldest, newest or shortest (path)
+///
+/// Files without a creation time go last so they are never the kept copy.
+fn order_by_keep_rule(group: &mut DupeGroup, keep: &str) -> Result<()> {
+    let files = &mut group.files;
+    match keep {
+        "shortest" => {
+            files.sort_by(|a, b| a.path.len().cmp(&b.path.len()).then(a.path.cmp(&b.path)))
+        }
+        "oldest" => files.sort_by_key(|f| (f.created_at.is_none(), f.created_at)),
+        "newest" => files.sort_by_key(|f| (f.created_at.is_none(), Reverse(f.created_at))),
+        other => {
+            return Err(Error::InvalidResponse(format!(
+                "Unknown keep rule: {} (use oldest, newest or shortest)",
+                other
+            )));
+        }
+    }
+    Ok(())
+}
@@ -1,13 +1,43 @@
 use crate::utils::format_bytes;
-use cloudreve_api::{CloudreveAPI, Result};
-use log::info;
+use crate::utils::remote::{self, RemoteFileDetails};
+use cloudreve_api::{CloudreveAPI, Result, UnifiedClient};
+use log::{info, warn};
+use serde_json::{Value, json};
 
-pub async fn handle_info(api: &CloudreveAPI, uri: String, extended: bool) -> Result<()> {
-    info!("Getting file info for: {}", uri);
-    info!("API Version: {}", api.api_version());
+pub async fn handle_info(
+    api: &CloudreveAPI,
+    uri: String,
+    extended: bool,
+    json: bool,
+) -> Result<()> {
+    if extended && let UnifiedClient::V4(client) = api.inner() {
+        let details = remote::get_details(client, &uri).await?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&details)?);
+        } else {
+            print_extended(&details);
+        }
+        return Ok(());
+    }
 
     let file_info = api.get_file_info(&uri).await?;
 
+    if json {
+        let output = json!({
+            "name": file_info.name(),
+            "path": file_info.path(),
+            "is_folder": file_info.is_folder(),
+            "size": file_info.size(),
+            "created_at": file_info.created_at(),
+            "updated_at": file_info.updated_at(),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    info!("Getting file info for: {}", uri);
+    info!("API Version: {}", api.api_version());
+
     info!("");
     info!("📄 File Information:");
     info!("  Name: {}", file_info.name());
@@ -25,11 +55,147 @@ pub async fn handle_info(api: &CloudreveAPI, uri: String, extended: bool) -> Res
     info!("  Updated: {}", file_info.updated_at());
 
     if extended {
-        info!("");
-        info!("📋 Extended Info:");
-        info!("  Extended information not yet fully implemented");
-        info!("  Raw data available via version-specific clients");
+        warn!("Extended information is only available on V4 servers");
     }
 
     Ok(())
 }
+
+fn print_extended(details: &RemoteFileDetails) {
+    let file = &details.file;
+    let ext = details.extended_info.clone().unwrap_or_default();
+
+    info!("📄 File Information:");
+    info!("  Name: {}", file.name);
+    info!("  Path: {}", file.display_path());
+    info!("  URI:  {}", file.path);
+    info!("  ID:   {}", file.id);
+    info!(
+        "  Type: {}",
+        if file.is_folder() { "Folder" } else { "File" }
+    );
+    info!("  Size: {}", format_bytes(file.size));
+    info!("  Created: {}", file.created_at);
+    info!("  Updated: {}", file.updated_at);
+    info!("  Owned: {}", if file.owned { "yes" } else { "no" });
+
+    info!("");
+    info!("💾 Storage:");
+    info!(
+        "  Policy: {}{}",
+        ext.policy_name().unwrap_or("(unknown)"),
+        if ext.storage_policy_inherited {
+            " (inherited)"
+        } else {
+            ""
+        }
+    );
+    info!("  Used:   {}", format_bytes(ext.storage_used));
+    if !file.is_folder() {
+        info!(
+            "  Thumbnail: {}",
+            if ext.has_thumbnail() {
+                "available"
+            } else {
+                "none"
+            }
+        );
+    }
+
+    if !ext.entities.is_empty() {
+        let versions = ext.entities.iter().filter(|e| e.is_version()).count();
+        info!("");
+        info!(
+            "🧱 Blobs ({}, {} version(s)):",
+            ext.entities.len(),
+            versions
+        );
+        for entity in &ext.entities {
+            let kind = match entity.entity_type {
+                0 => "version",
+                1 => "thumbnail",
+                2 => "live photo",
+                _ => "other",
+            };
+            let current = if file.primary_entity.as_deref() == Some(entity.id.as_str()) {
+                " (current)"
+            } else {
+                ""
+            };
+            info!(
+                "  - {} [{}] {} {}{}",
+                entity.id,
+                kind,
+                format_bytes(entity.size),
+                entity.created_at,
+                current
+            );
+            if let Some(policy) = entity.policy_name() {
+                info!("      Policy: {}", policy);
+            }
+        }
+    }
+
+    if let Some(permissions) = &ext.permissions {
+        info!("");
+        info!("🔐 Permissions:");
+        if let Some(object) = permissions.as_object() {
+            for (key, value) in object {
+                info!("  {}: {}", key, compact(value));
+            }
+        }
+    }
+
+    if !ext.shares.is_empty() {
+        info!("");
+        info!("🔗 Shares ({}):", ext.shares.len());
+        for share in &ext.shares {
+            info!(
+                "  - {} {} (views: {}, expired: {})",
+                field(share, "id"),
+                field(share, "url"),
+                field(share, "visited"),
+                field(share, "expired")
+            );
+        }
+    }
+
+    if !ext.direct_links.is_empty() {
+        info!("");
+        info!("⛓  Direct Links ({}):", ext.direct_links.len());
+        for link in &ext.direct_links {
+            info!(
+                "  - {} (downloads: {})",
+                field(link, "url"),
+                field(link, "downloaded")
+            );
+        }
+    }
+
+    if let Some(metadata) = &file.metadata
+        && !metadata.is_empty()
+    {
+        let mut entries: Vec<_> = metadata.iter().collect();
+        entries.sort();
+        info!("");
+        info!("🏷  Metadata:");
+        for (key, value) in entries {
+            info!("  {} = {}", key, value);
+        }
+    }
+}
+
+/// Reads a field as display text, `-` when absent
+fn field(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .map(compact)
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn compact(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
@@ -1,13 +1,73 @@
-use crate::utils::format_bytes;
-use cloudreve_api::{CloudreveAPI, FileListAll, Result};
+use crate::utils::remote::{self, RemoteFile};
+use crate::utils::{format_bytes, time, walk};
+use cloudreve_api::{CloudreveAPI, FileListAll, Result, UnifiedClient};
 use log::info;
+use std::collections::VecDeque;
+
+/// Output options beyond the plain listing
+#[derive(Debug, Clone, Default)]
+pub struct ListOptions {
+    /// Long format with type, size, dates, owner, ID and policy
+    pub long: bool,
+    /// Sort key: name, size or modified
+    pub sort: Option<String>,
+    pub reverse: bool,
+    /// Only "files" or "folders"
+    pub only: Option<String>,
+    pub recursive: bool,
+    /// Print pages as they arrive instead of buffering
+    pub all_pages: bool,
+}
+
+impl ListOptions {
+    fn is_plain(&self) -> bool {
+        !self.long
+            && self.sort.is_none()
+            && !self.reverse
+            && self.only.is_none()
+            && !self.recursive
+            && !self.all_pages
+    }
+}
+
+/// A listed item with every field either API version provides
+struct Entry {
+    name: String,
+    path: String,
+    is_folder: bool,
+    size: i64,
+    created: Option<String>,
+    modified: Option<String>,
+    id: Option<String>,
+    owned: Option<bool>,
+}
+
+impl Entry {
+    fn from_remote(dir: &str, file: RemoteFile) -> Self {
+        Entry {
+            path: walk::join_path(dir, &file.name),
+            is_folder: file.is_folder(),
+            size: file.size,
+            created: Some(file.created_at),
+            modified: Some(file.updated_at),
+            id: Some(file.id),
+            owned: Some(file.owned),
+            name: file.name,
+        }
+    }
+}
 
 pub async fn handle_list(
     api: &CloudreveAPI,
     path: String,
     page: Option<u32>,
     page_size: Option<u32>,
+    options: ListOptions,
 ) -> Result<()> {
+    if !options.is_plain() {
+        return handle_rich_list(api, path, page_size, options).await;
+    }
+
     info!("Listing files in path: {}", path);
 
     // If page is specified, use single page listing; otherwise fetch all pages
@@ -72,3 +132,156 @@ pub async fn handle_list(
 
     Ok(())
 }
+
+/// Listing with long format, sorting, filtering, recursion or streaming
+async fn handle_rich_list(
+    api: &CloudreveAPI,
+    path: String,
+    page_size: Option<u32>,
+    options: ListOptions,
+) -> Result<()> {
+    let page_size = page_size.unwrap_or(100);
+    let mut queue = VecDeque::from([walk::normalize_path(&path)]);
+    let mut total = 0;
+
+    while let Some(dir) = queue.pop_front() {
+        if options.recursive {
+            println!("{}:", dir);
+        }
+
+        match api.inner() {
+            UnifiedClient::V4(client) if options.all_pages => {
+                // Print each page as soon as it arrives; sorting is per page
+                let mut next_token: Option<String> = None;
+                loop {
+                    let page =
+                        remote::list_page(client, &dir, page_size, next_token.as_deref()).await?;
+                    let entries: Vec<Entry> = page
+                        .files
+                        .into_iter()
+                        .map(|f| Entry::from_remote(&dir, f))
+                        .collect();
+                    total += print_entries(entries, &options, page.policy.as_deref(), &mut queue);
+
+                    match page.next_token {
+                        Some(token) => next_token = Some(token),
+                        None => break,
+                    }
+                }
+            }
+            UnifiedClient::V4(client) => {
+                let mut page = remote::list_page(client, &dir, page_size, None).await?;
+                let policy = page.policy.take();
+                let mut files = page.files;
+                let mut next_token = page.next_token;
+                while let Some(token) = next_token {
+                    let page = remote::list_page(client, &dir, page_size, Some(&token)).await?;
+                    files.extend(page.files);
+                    next_token = page.next_token;
+                }
+                let entries: Vec<Entry> = files
+                    .into_iter()
+                    .map(|f| Entry::from_remote(&dir, f))
+                    .collect();
+                total += print_entries(entries, &options, policy.as_deref(), &mut queue);
+            }
+            UnifiedClient::V3(_) => {
+                // V3 returns whole folders at once and lacks dates and IDs
+                let list = api.list_files_all(&dir, Some(page_size)).await?;
+                let entries: Vec<Entry> = list
+                    .items()
+                    .into_iter()
+                    .map(|item| Entry {
+                        name: item.name.clone(),
+                        path: walk::join_path(&dir, &item.name),
+                        is_folder: item.is_folder,
+                        size: item.size,
+                        created: None,
+                        modified: None,
+                        id: None,
+                        owned: None,
+                    })
+                    .collect();
+                total += print_entries(entries, &options, None, &mut queue);
+            }
+        }
+
+        if options.recursive && !queue.is_empty() {
+            println!();
+        }
+    }
+
+    info!("Total: {} items", total);
+    Ok(())
+}
+
+/// Filters, sorts and prints entries, queueing subfolders when recursive
+fn print_entries(
+    mut entries: Vec<Entry>,
+    options: &ListOptions,
+    policy: Option<&str>,
+    queue: &mut VecDeque<String>,
+) -> usize {
+    if options.recursive {
+        queue.extend(
+            entries
+                .iter()
+                .filter(|e| e.is_folder)
+                .map(|e| e.path.clone()),
+        );
+    }
+
+    match options.only.as_deref() {
+        Some("files") => entries.retain(|e| !e.is_folder),
+        Some("folders") => entries.retain(|e| e.is_folder),
+        _ => {}
+    }
+
+    match options.sort.as_deref() {
+        Some("size") => entries.sort_by_key(|e| e.size),
+        Some("modified") => entries.sort_by(|a, b| a.modified.cmp(&b.modified)),
+        Some(_) => entries.sort_by_key(|e| e.name.to_lowercase()),
+        None => {}
+    }
+    if options.reverse {
+        entries.reverse();
+    }
+
+    for entry in &entries {
+        if options.long {
+            println!(
+                "{} {:>10}  {:16}  {:16}  {:5}  {:>12}  {:10}  {}{}",
+                if entry.is_folder { "d" } else { "-" },
+                if entry.is_folder {
+                    "-".to_string()
+                } else {
+                    format_bytes(entry.size)
+                },
+                format_time(entry.modified.as_deref()),
+                format_time(entry.created.as_deref()),
+                match entry.owned {
+                    Some(true) => "me",
+                    Some(false) => "other",
+                    None => "-",
+                },
+                entry.id.as_deref().unwrap_or("-"),
+                policy.unwrap_or("-"),
+                entry.name,
+                if entry.is_folder { "/" } else { "" }
+            );
+        } else if entry.is_folder {
+            println!("{}/", entry.name);
+        } else {
+            println!("{}", entry.name);
+        }
+    }
+
+    entries.len()
+}
+
+fn format_time(value: Option<&str>) -> String {
+    value
+        .and_then(time::parse_remote_time)
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
@@ -4,6 +4,8 @@ pub mod copy;
 pub mod delete;
 pub mod diff;
 pub mod download;
+pub mod du;
+pub mod dupes;
 pub mod info;
 pub mod list;
 pub mod metadata;
@@ -12,12 +14,20 @@ pub mod move_cmd;
 pub mod permission;
 pub mod preview;
 pub mod rename;
+pub mod report;
 pub mod restore;
 pub mod search;
 pub mod share;
 pub mod sync;
+pub mod tree;
 pub mod upload;
+pub mod versions;
 
+use crate::commands::share::{ShareOptions, url_with_password};
+use crate::config::Config;
+use crate::utils::credentials::{self, SecretSource};
+use crate::utils::qr::QrOptions;
+use crate::utils::time;
 use cloudreve_api::api::v4::models::FileType;
 use cloudreve_api::{CloudreveAPI, Result, UnifiedClient};
 
@@ -36,6 +46,130 @@ pub enum FileCommands {
         /// Items per page
         #[clap(long, default_value = "50")]
         page_size: Option<u32>,
+
+        /// Long format: type, size, modified, created, owner, ID, policy
+        #[clap(short, long, conflicts_with = "page")]
+        long: bool,
+
+        /// Sort by name, size or modified
+        #[clap(long, conflicts_with = "page", value_parser = ["name", "size", "modified"])]
+        sort: Option<String>,
+
+        /// Reverse the sort order
+        #[clap(short, long, conflicts_with = "page")]
+        reverse: bool,
+
+        /// Show only files or folders
+        #[clap(long, conflicts_with = "page", value_parser = ["files", "folders"])]
+        only: Option<String>,
+
+        /// List subfolders recursively
+        #[clap(short = 'R', long, conflicts_with = "page")]
+        recursive: bool,
+
+        /// Fetch every page, printing each as it arrives (sorting applies per page)
+        #[clap(long, conflicts_with = "page")]
+        all_pages: bool,
+    },
+
+    /// Show a folder as a recursive tree
+    Tree {
+        /// Folder path
+        #[clap(default_value = "/")]
+        path: String,
+
+        /// Maximum depth to descend (default: unlimited)
+        #[clap(short, long)]
+        depth: Option<usize>,
+
+        /// Show folders only
+        #[clap(long)]
+        dirs_only: bool,
+
+        /// Show file sizes
+        #[clap(short, long)]
+        size: bool,
+
+        /// Folders listed concurrently (default: 5, 0 = unlimited)
+        #[clap(short, long, default_value = "5")]
+        concurrency: usize,
+    },
+
+    /// Summarize disk usage per subfolder
+    Du {
+        /// Folder path
+        #[clap(default_value = "/")]
+        path: String,
+
+        /// Show subfolders up to this depth
+        #[clap(short = 'd', long, default_value = "1")]
+        max_depth: usize,
+
+        /// Print sizes in bytes instead of human-readable units
+        #[clap(short, long)]
+        bytes: bool,
+
+        /// Folders listed concurrently (default: 5, 0 = unlimited)
+        #[clap(short, long, default_value = "5")]
+        concurrency: usize,
+    },
+
+    /// Find duplicate files by size and content digest
+    Dupes {
+        /// Folder to scan
+        #[clap(default_value = "/")]
+        path: String,
+
+        /// Ignore files smaller than this many bytes
+        #[clap(long, default_value = "1")]
+        min_size: i64,
+
+        /// Copy to keep in each group: oldest, newest or shortest (path)
+        #[clap(long, default_value = "oldest", value_parser = ["oldest", "newest", "shortest"])]
+        keep: String,
+
+        /// Group by size only, skipping the content digest (report only)
+        #[clap(long, conflicts_with_all = ["delete", "move_to"])]
+        size_only: bool,
+
+        /// Delete (move to trash) all but the kept copy
+        #[clap(long, conflicts_with = "move_to")]
+        delete: bool,
+
+        /// Move all but the kept copy under this folder, keeping their relative folders
+        #[clap(long)]
+        move_to: Option<String>,
+
+        /// Skip confirmation for --delete
+        #[clap(long, requires = "delete")]
+        force: bool,
+
+        /// Concurrent listings and downloads (default: 5, 0 = unlimited)
+        #[clap(short, long, default_value = "5")]
+        concurrency: usize,
+    },
+
+    /// Storage usage report by folder, extension, policy and age
+    Report {
+        /// Folder to report on
+        #[clap(default_value = "/")]
+        path: String,
+
+        /// Number of largest files to list
+        #[clap(long, default_value = "20")]
+        top: usize,
+
+        /// Output format: text, json or csv
+        #[clap(long, default_value = "text", value_parser = ["text", "json", "csv"])]
+        format: String,
+
+        /// Write json/csv output to this file instead of stdout
+        #[clap(short, long)]
+        output: Option<String>,
+
+        /// Folders listed concurrently (default: 5, 0 = unlimited)
+        #[clap(short, long, default_value = "5")]
+        concurrency: usize,
     },
 
     /// Get file information
@@ -44,9 +178,13 @@ pub enum FileCommands {
         #[clap(short, long)]
         path: String,
 
-        /// Include extended information
+        /// Include extended information: storage, blobs, versions, permissions, shares, links (V4)
         #[clap(short, long)]
         extended: bool,
+
+        /// Print as JSON
+        #[clap(long)]
+        json: bool,
     },
 
     /// Upload a file
@@ -55,15 +193,15 @@ pub enum FileCommands {
         #[clap(short, long, required = true, num_args = 1..)]
         file: Vec<String>,
 
-        /// Destination path
-        #[clap(short = 'p', long, default_value = "/")]
-        path: String,
+        /// Destination path [default: default_upload_path from config, or /]
+        #[clap(short = 'p', long)]
+        path: Option<String>,
 
         /// Overwrite if file exists
         #[clap(long)]
         overwrite: bool,
 
-        /// Storage policy ID (uses first available if not specified)
+        /// Storage policy ID (default_policy from config, or first available)
         #[clap(long)]
         policy: Option<String>,
 
@@ -82,9 +220,9 @@ pub enum FileCommands {
         #[clap(short = 'f', long, required = true, num_args = 1..)]
         file: Vec<String>,
 
-        /// Local output directory
-        #[clap(short = 'p', long, default_value = ".")]
-        output: String,
+        /// Local output directory [default: default_download_dir from config, or .]
+        #[clap(short = 'p', long)]
+        output: Option<String>,
 
         /// Download URL expiration time in seconds
         #[clap(long)]
@@ -110,9 +248,17 @@ pub enum FileCommands {
         #[clap(long, short = 'f')]
         force: bool,
 
-        /// Recursive deletion for directories
+        /// Delete non-empty folders with their contents
         #[clap(short, long)]
         recursive: bool,
+
+        /// Delete permanently instead of moving to trash (V4)
+        #[clap(long)]
+        permanent: bool,
+
+        /// Remove only the file entry, keeping blobs still referenced elsewhere (V4)
+        #[clap(long)]
+        unlink: bool,
     },
 
     /// Rename a file
@@ -155,7 +301,7 @@ pub enum FileCommands {
         path: String,
     },
 
-    /// Restore files from trash
+    /// Restore files from trash (see `trash list` for URIs)
     Restore {
         /// File URI(s) to restore
         #[clap(long, required = true)]
@@ -168,6 +314,18 @@ pub enum FileCommands {
         command: permission::PermissionCommands,
     },
 
+    /// File version history (V4)
+    ///
+    /// `file versions <path>` is short for `file versions list <path>`.
+    #[clap(args_conflicts_with_subcommands = true)]
+    Versions {
+        /// File path
+        path: Option<String>,
+
+        #[clap(subcommand)]
+        command: Option<versions::VersionsCommands>,
+    },
+
     /// Metadata management
     Metadata {
         #[clap(subcommand)]
@@ -189,8 +347,22 @@ pub enum FileCommands {
         expire: Option<u32>,
 
         /// Password for the share link
-        #[clap(short('P'), long)]
+        #[clap(short('P'), long, conflicts_with_all = ["password_stdin", "password_file"])]
         password: Option<String>,
+
+        /// Read the share password from the first line of stdin
+        #[clap(long, conflicts_with = "password_file")]
+        password_stdin: bool,
+
+        /// Read the share password from a file
+        #[clap(long)]
+        password_file: Option<String>,
+
+        #[clap(flatten)]
+        options: ShareOptions,
+
+        #[clap(flatten)]
+        qr: QrOptions,
     },
 
     /// Search files
@@ -222,6 +394,62 @@ pub enum FileCommands {
         /// Recursive search
         #[clap(short, long)]
         recursive: bool,
+
+        /// Category: image, video, audio or document (V4)
+        #[clap(long, value_parser = ["image", "video", "audio", "document"])]
+        category: Option<String>,
+
+        /// Name matches this regular expression
+        #[clap(long)]
+        regex: Option<String>,
+
+        /// Metadata match as key=value, or key to require the key, repeatable (V4)
+        #[clap(long = "meta", value_name = "KEY[=VALUE]")]
+        metadata: Vec<String>,
+
+        /// Tag that must be present, repeatable (V4)
+        #[clap(long)]
+        tag: Vec<String>,
+
+        /// Created at or after (YYYY-MM-DD, RFC 3339, or an age like 7d) (V4)
+        #[clap(long)]
+        created_after: Option<String>,
+
+        /// Created at or before (V4)
+        #[clap(long)]
+        created_before: Option<String>,
+
+        /// Modified at or after (V4)
+        #[clap(long)]
+        modified_after: Option<String>,
+
+        /// Modified at or before (V4)
+        #[clap(long)]
+        modified_before: Option<String>,
+
+        /// Print only full paths, one per line
+        #[clap(long)]
+        paths_only: bool,
+
+        /// Print full paths separated by NUL (for xargs -0)
+        #[clap(long, conflicts_with = "paths_only")]
+        print0: bool,
+
+        /// Run an action on every match: download, delete, move or share
+        #[clap(long, value_parser = ["download", "delete", "move", "share"])]
+        exec: Option<String>,
+
+        /// Download directory or move destination for --exec
+        #[clap(long, requires = "exec")]
+        exec_target: Option<String>,
+
+        /// Skip confirmation for --exec delete
+        #[clap(long, requires = "exec")]
+        force: bool,
+
+        /// Let --exec delete remove non-empty folders
+        #[clap(long, requires = "exec")]
+        exec_recursive: bool,
     },
 
     /// Sync files between local and remote
@@ -266,15 +494,40 @@ pub enum FileCommands {
     },
 }
 
-pub async fn handle_file_command(client: &CloudreveAPI, command: FileCommands) -> Result<()> {
+pub async fn handle_file_command(
+    client: &CloudreveAPI,
+    http_client: &reqwest::Client,
+    cfg: &Config,
+    command: FileCommands,
+) -> Result<()> {
     match command {
         FileCommands::List {
             path,
             page,
             page_size,
-        } => list::handle_list(client, path, page, page_size).await,
+            long,
+            sort,
+            reverse,
+            only,
+            recursive,
+            all_pages,
+        } => {
+            let options = list::ListOptions {
+                long,
+                sort,
+                reverse,
+                only,
+                recursive,
+                all_pages,
+            };
+            list::handle_list(client, path, page, page_size, options).await
+        }
 
-        FileCommands::Info { path, extended } => info::handle_info(client, path, extended).await,
+        FileCommands::Info {
+            path,
+            extended,
+            json,
+        } => info::handle_info(client, path, extended, json).await,
 
         FileCommands::Upload {
             file,
@@ -284,6 +537,10 @@ pub async fn handle_file_command(client: &CloudreveAPI, command: FileCommands) -
             recursive,
             concurrency,
         } => {
+            let path = path
+                .or_else(|| cfg.default_upload_path.clone())
+                .unwrap_or_else(|| "/".to_string());
+            let policy = policy.or_else(|| cfg.default_policy.clone());
             upload::handle_upload(
                 client,
                 file,
@@ -302,13 +559,38 @@ pub async fn handle_file_command(client: &CloudreveAPI, command: FileCommands) -
             expires_in,
             concurrency,
             batch,
-        } => download::handle_download(client, file, output, expires_in, concurrency, batch).await,
+        } => {
+            let output = match output {
+                Some(output) => output,
+                None => {
+                    // The configured download directory is always treated as a directory
+                    let dir = cfg
+                        .default_download_dir
+                        .clone()
+                        .unwrap_or_else(|| ".".to_string());
+                    std::fs::create_dir_all(&dir)?;
+                    dir
+                }
+            };
+            download::handle_download(
+                client,
+                http_client,
+                file,
+                output,
+                expires_in,
+                concurrency,
+                batch,
+            )
+            .await
+        }
 
         FileCommands::Delete {
             path,
             force,
             recursive,
-        } => delete::handle_delete(client, path, force, recursive).await,
+            permanent,
+            unlink,
+        } => delete::handle_delete(client, path, force, recursive, permanent, unlink).await,
 
         FileCommands::Rename { src, name } => rename::handle_rename(client, src, name).await,
 
@@ -340,6 +622,26 @@ pub async fn handle_file_command(client: &CloudreveAPI, command: FileCommands) -
             }
         }
 
+        FileCommands::Versions { path, command } => {
+            let command = match (command, path) {
+                (Some(command), _) => command,
+                (None, Some(path)) => versions::VersionsCommands::List { path },
+                (None, None) => {
+                    return Err(cloudreve_api::Error::InvalidResponse(
+                        "Specify a file path or a versions subcommand".to_string(),
+                    ));
+                }
+            };
+            match client.inner() {
+                UnifiedClient::V4(v4_client) => {
+                    versions::handle_versions(v4_client, http_client, command).await
+                }
+                UnifiedClient::V3(_) => Err(cloudreve_api::Error::InvalidResponse(
+                    "Versions not yet supported for V3 API".to_string(),
+                )),
+            }
+        }
+
         FileCommands::Metadata { command } => {
             // Use V4 client for now (not yet migrated to CloudreveAPI)
             match client.inner() {
@@ -355,7 +657,68 @@ pub async fn handle_file_command(client: &CloudreveAPI, command: FileCommands) -
             name,
             expire,
             password,
-        } => share::handle_share(client, path, name, expire, password).await,
+            password_stdin,
+            password_file,
+            options,
+            qr,
+        } => {
+            let password = credentials::resolve_secret(SecretSource {
+                value: password,
+                from_stdin: password_stdin,
+                file: password_file,
+                env_var: None,
+            })?;
+            let url =
+                share::handle_share(client, path, name, expire, password.clone(), options).await?;
+            qr.show(&url_with_password(&url, password.as_deref()))
+        }
+
+        FileCommands::Tree {
+            path,
+            depth,
+            dirs_only,
+            size,
+            concurrency,
+        } => tree::handle_tree(client, path, depth, dirs_only, size, concurrency).await,
+
+        FileCommands::Du {
+            path,
+            max_depth,
+            bytes,
+            concurrency,
+        } => du::handle_du(client, path, max_depth, bytes, concurrency).await,
+
+        FileCommands::Dupes {
+            path,
+            min_size,
+            keep,
+            size_only,
+            delete,
+            move_to,
+            force,
+            concurrency,
+        } => {
+            let options = dupes::DupesOptions {
+                min_size,
+                keep,
+                size_only,
+                concurrency,
+            };
+            let action = match (delete, move_to) {
+                (true, _) => dupes::DupesAction::Delete { force },
+                (false, Some(dest)) => dupes::DupesAction::Move { dest },
+                (false, None) => dupes::DupesAction::Report,
+            };
+            dupes::handle_dupes(client, http_client, path, options, action).await
+        }
+
+        FileCommands::Report {
+            path,
+            top,
+            format,
+            output,
+            concurrency,
+        } => report::handle_report(client, path, top, format, output, concurrency).await,
 
         FileCommands::Search {
             path,
@@ -365,27 +728,73 @@ pub async fn handle_file_command(client: &CloudreveAPI, command: FileCommands) -
             max_size,
             extension,
             recursive,
+            regex,
+            category,
+            metadata,
+            tag,
+            created_after,
+            created_before,
+            modified_after,
+            modified_before,
+            paths_only,
+            print0,
+            exec,
+            exec_target,
+            force,
+            exec_recursive,
         } => {
-            // Use V4 client for now (not yet migrated to CloudreveAPI)
-            match client.inner() {
-                UnifiedClient::V4(v4_client) => {
-                    let filter = search::SearchFilter {
-                        name_pattern: name,
-                        file_type: type_.and_then(|t| match t.as_str() {
-                            "file" => Some(FileType::File),
-                            "folder" => Some(FileType::Folder),
-                            _ => None,
-                        }),
-                        min_size,
-                        max_size,
-                        extension,
-                    };
-                    search::handle_search(v4_client, path, filter, recursive).await
-                }
-                UnifiedClient::V3(_) => Err(cloudreve_api::Error::InvalidResponse(
-                    "Search not yet supported for V3 API".to_string(),
-                )),
-            }
+            let parse_time = |spec: Option<String>| {
+                spec.map(|s| time::parse_time_spec(&s))
+                    .transpose()
+                    .map_err(cloudreve_api::Error::InvalidResponse)
+            };
+            let metadata = metadata
+                .iter()
+                .map(|pair| match pair.split_once('=') {
+                    Some((key, value)) => (key.to_string(), value.to_string()),
+                    None => (pair.clone(), String::new()),
+                })
+                .collect();
+            let regex = regex
+                .map(|re| regex::Regex::new(&re))
+                .transpose()
+                .map_err(|e| {
+                    cloudreve_api::Error::InvalidResponse(format!("Invalid regex: {}", e))
+                })?;
+
+            let filter = search::SearchFilter {
+                name_pattern: name,
+                regex,
+                file_type: type_.and_then(|t| match t.as_str() {
+                    "file" => Some(FileType::File),
+                    "folder" => Some(FileType::Folder),
+                    _ => None,
+                }),
+                min_size,
+                max_size,
+                extension,
+                category,
+                metadata,
+                tags: tag,
+                created_after: parse_time(created_after)?,
+                created_before: parse_time(created_before)?,
+                modified_after: parse_time(modified_after)?,
+                modified_before: parse_time(modified_before)?,
+            };
+            let output = if print0 {
+                search::SearchOutput::Print0
+            } else if paths_only {
+                search::SearchOutput::Paths
+            } else {
+                search::SearchOutput::Pretty
+            };
+            let exec = exec.map(|action| search::SearchExec {
+                action,
+                target: exec_target,
+                force,
+                recursive: exec_recursive,
+            });
+            search::handle_search(client, http_client, path, filter, recursive, output, exec).await
         }
 
         FileCommands::Sync {
@@ -405,7 +814,9 @@ pub async fn handle_file_command(client: &CloudreveAPI, command: FileCommands) -
             }
         }
 
-        FileCommands::Preview { uri, type_ } => preview::handle_preview(client, uri, type_).await,
+        FileCommands::Preview { uri, type_ } => {
+            preview::handle_preview(client, http_client, uri, type_).await
+        }
 
         FileCommands::Diff { local, remote } => {
             // Use V4 client for now (not yet migrated to CloudreveAPI)
@@ -14,12 +14,17 @@ pub async fn handle_move(api: &CloudreveAPI, src: Vec<String>, dest: String) ->
         return Err(Error::InvalidResponse("No files matched".to_string()));
     }
 
+    move_paths(api, &expanded_files, &dest).await
+}
+
+/// 移动指定路径到 `dest`，不展开名称中的通配符
+pub async fn move_paths(api: &CloudreveAPI, paths: &[String], dest: &str) -> Result<()> {
     // 使用并发控制执行批量移动
-    let tasks: Vec<_> = expanded_files
+    let tasks: Vec<_> = paths
         .iter()
         .map(|file_path| {
             let api = api.clone();
-            let dest = dest.clone();
+            let dest = dest.to_string();
             let file_path = file_path.clone();
             (file_path.clone(), async move {
                 api.move_file(&file_path, &dest).await
@@ -51,7 +56,7 @@ pub async fn handle_move(api: &CloudreveAPI, src: Vec<String>, dest: String) ->
         return Err(Error::InvalidResponse(format!(
             "Failed to move {} out of {} files",
             failed,
-            expanded_files.len()
+            paths.len()
         )));
     }
 
@@ -1,14 +1,20 @@
 use cloudreve_api::{CloudreveAPI, Result};
 use log::{error, info};
+use reqwest::Client;
 
-pub async fn handle_preview(api: &CloudreveAPI, uri: String, preview_type: String) -> Result<()> {
+pub async fn handle_preview(
+    api: &CloudreveAPI,
+    http_client: &Client,
+    uri: String,
+    preview_type: String,
+) -> Result<()> {
     info!("Previewing: {} (type: {})", uri, preview_type);
 
     // For preview, we need to get the download URL first
     match api.download_file(&uri).await {
         Ok(download_url) => match preview_type.as_str() {
-            "text" => preview_text(&download_url).await?,
-            "json" => preview_json(&download_url).await?,
+            "text" => preview_text(http_client, &download_url).await?,
+            "json" => preview_json(http_client, &download_url).await?,
             "image" => preview_image(&download_url).await?,
             _ => {
                 error!("Unsupported preview type: {}", preview_type);
@@ -35,8 +41,7 @@ pub async fn handle_preview(api: &CloudreveAPI, uri: String, preview_type: Strin
     Ok(())
 }
 
-async fn preview_text(url: &str) -> Result<()> {
-    let http_client = reqwest::Client::new();
+async fn preview_text(http_client: &Client, url: &str) -> Result<()> {
     let response = http_client.get(url).send().await?;
 
     if !response.status().is_success() {
@@ -61,8 +66,7 @@ async fn preview_text(url: &str) -> Result<()> {
     Ok(())
 }
 
-async fn preview_json(url: &str) -> Result<()> {
-    let http_client = reqwest::Client::new();
+async fn preview_json(http_client: &Client, url: &str) -> Result<()> {
     let response = http_client.get(url).send().await?;
 
     if !response.status().is_success() {
@@ -0,0 +1,291 @@
+use crate::utils::{csv_field, format_bytes, time, walk};
+use chrono::{DateTime, Utc};
+use cloudreve_api::{CloudreveAPI, Result};
+use log::{info, warn};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs;
+
+/// A file counted by the report
+struct ReportFile {
+    path: String,
+    size: i64,
+    modified: Option<DateTime<Utc>>,
+    policy: Option<String>,
+}
+
+/// Bytes and file count for one key of a dimension
+#[derive(Serialize)]
+struct Bucket {
+    key: String,
+    files: usize,
+    bytes: i64,
+}
+
+#[derive(Serialize)]
+struct LargeFile {
+    path: String,
+    bytes: i64,
+    modified: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Report {
+    root: String,
+    total_files: usize,
+    total_bytes: i64,
+    by_extension: Vec<Bucket>,
+    by_folder: Vec<Bucket>,
+    by_policy: Vec<Bucket>,
+    by_age: Vec<Bucket>,
+    largest: Vec<LargeFile>,
+}
+
+/// Age buckets by last modification, upper bounds in days
+const AGE_BUCKETS: [(&str, i64); 5] = [
+    ("< 30 days", 30),
+    ("30-90 days", 90),
+    ("90 days-1 year", 365),
+    ("1-3 years", 365 * 3),
+    ("> 3 years", i64::MAX),
+];
+
+pub async fn handle_report(
+    api: &CloudreveAPI,
+    path: String,
+    top: usize,
+    format: String,
+    output: Option<String>,
+    concurrency: usize,
+) -> Result<()> {
+    let root = walk::normalize_path(&path);
+    info!("Building storage report for {}...", root);
+
+    // V3 listings carry neither dates nor policies, those count as unknown
+    let files = walk::walk(api, &root, None, concurrency)
+        .await?
+        .into_iter()
+        .filter(|n| !n.is_folder)
+        .map(|n| ReportFile {
+            modified: n.updated_at.as_deref().and_then(time::parse_remote_time),
+            path: n.path,
+            size: n.size,
+            policy: n.policy,
+        })
+        .collect();
+
+    let report = build_report(&root, files, top);
+
+    let rendered = match format.as_str() {
+        "json" => serde_json::to_string_pretty(&report)?,
+        "csv" => render_csv(&report),
+        _ => {
+            print_text(&report);
+            if output.is_some() {
+                warn!("--output is only used with --format json or csv");
+            }
+            return Ok(());
+        }
+    };
+
+    match output {
+        Some(file) => {
+            fs::write(&file, rendered)?;
+            info!("Report written to {}", file);
+        }
+        None => println!("{}", rendered),
+    }
+
+    Ok(())
+}
+
+fn build_report(root: &str, mut files: Vec<ReportFile>, top: usize) -> Report {
+    let now = Utc::now();
+    let mut by_extension: HashMap<String, Bucket> = HashMap::new();
+    let mut by_folder: HashMap<String, Bucket> = HashMap::new();
+    let mut by_policy: HashMap<String, Bucket> = HashMap::new();
+    let mut by_age: HashMap<String, Bucket> = HashMap::new();
+
+    let add = |map: &mut HashMap<String, Bucket>, key: String, size: i64| {
+        let bucket = map.entry(key.clone()).or_insert(Bucket {
+            key,
+            files: 0,
+            bytes: 0,
+        });
+        bucket.files += 1;
+        bucket.bytes += size;
+    };
+
+    for file in &files {
+        add(&mut by_extension, extension_of(&file.path), file.size);
+        add(&mut by_folder, top_level_of(root, &file.path), file.size);
+        add(
+            &mut by_policy,
+            file.policy
+                .clone()
+                .unwrap_or_else(|| "(unknown)".to_string()),
+            file.size,
+        );
+
+        let age = match file.modified {
+            Some(modified) => {
+                let days = (now - modified).num_days();
+                AGE_BUCKETS
+                    .iter()
+                    .find(|(_, max)| days < *max)
+                    .map(|(label, _)| label.to_string())
+                    .unwrap_or_default()
+            }
+            None => "(unknown)".to_string(),
+        };
+        add(&mut by_age, age, file.size);
+    }
+
+    let sorted = |map: HashMap<String, Bucket>| {
+        let mut buckets: Vec<Bucket> = map.into_values().collect();
+        buckets.sort_by(|a, b| b.bytes.cmp(&a.bytes).then(a.key.cmp(&b.key)));
+        buckets
+    };
+
+    // Age buckets keep their natural order
+    let mut age_buckets = Vec::new();
+    for (label, _) in AGE_BUCKETS {
+        if let Some(bucket) = by_age.remove(label) {
+            age_buckets.push(bucket);
+        }
+    }
+    age_buckets.extend(by_age.into_values());
+
+    let total_files = files.len();
+    let total_bytes = files.iter().map(|f| f.size).sum();
+
+    files.sort_by_key(|f| Reverse(f.size));
+    let largest = files
+        .into_iter()
+        .take(top)
+        .map(|f| LargeFile {
+            path: f.path,
+            bytes: f.size,
+            modified: f.modified.map(|m| m.to_rfc3339()),
+        })
+        .collect();
+
+    Report {
+        root: root.to_string(),
+        total_files,
+        total_bytes,
+        by_extension: sorted(by_extension),
+        by_folder: sorted(by_folder),
+        by_policy: sorted(by_policy),
+        by_age: age_buckets,
+        largest,
+    }
+}
+
+/// Lowercase extension, `(none)` for names without one
+fn extension_of(path: &str) -> String {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() && !ext.is_empty() => ext.to_lowercase(),
+        _ => "(none)".to_string(),
+    }
+}
+
+/// First folder below the report root, `(root)` for files directly in it
+fn top_level_of(root: &str, path: &str) -> String {
+    let relative = path
+        .strip_prefix(root)
+        .unwrap_or(path)
+        .trim_start_matches('/');
+    match relative.split_once('/') {
+        Some((folder, _)) => walk::join_path(root, folder),
+        None => "(root)".to_string(),
+    }
+}
+
+fn print_text(report: &Report) {
+    info!("");
+    info!(
+        "📊 {}: {} files, {}",
+        report.root,
+        report.total_files,
+        format_bytes(report.total_bytes)
+    );
+
+    let sections = [
+        ("By top-level folder", &report.by_folder),
+        ("By extension", &report.by_extension),
+        ("By storage policy", &report.by_policy),
+        ("By age (last modified)", &report.by_age),
+    ];
+    for (title, buckets) in sections {
+        info!("");
+        info!("{}:", title);
+        for bucket in buckets.iter().take(20) {
+            info!(
+                "  {:>12}  {:>8} files  {}",
+                format_bytes(bucket.bytes),
+                bucket.files,
+                bucket.key
+            );
+        }
+        if buckets.len() > 20 {
+            info!("  ... {} more", buckets.len() - 20);
+        }
+    }
+
+    if !report.largest.is_empty() {
+        info!("");
+        info!("Largest files:");
+        for file in &report.largest {
+            info!("  {:>12}  {}", format_bytes(file.bytes), file.path);
+        }
+    }
+}
+
+/// One row per bucket: `dimension,key,files,bytes`
+fn render_csv(report: &Report) -> String {
+    let mut csv = String::from("dimension,key,files,bytes\n");
+    let sections = [
+        ("folder", &report.by_folder),
+        ("extension", &report.by_extension),
+        ("policy", &report.by_policy),
+        ("age", &report.by_age),
+    ];
+    for (dimension, buckets) in sections {
+        for bucket in buckets {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                dimension,
+                csv_field(&bucket.key),
+                bucket.files,
+                bucket.bytes
+            ));
+        }
+    }
+    for file in &report.largest {
+        csv.push_str(&format!(
+            "largest,{},1,{}\n",
+            csv_field(&file.path),
+            file.bytes
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_keys() {
+        assert_eq!(extension_of("/a/Photo.JPG"), "jpg");
+        assert_eq!(extension_of("/a/.bashrc"), "(none)");
+        assert_eq!(extension_of("/a/README"), "(none)");
+
+        assert_eq!(top_level_of("/", "/docs/a/b.txt"), "/docs");
+        assert_eq!(top_level_of("/docs", "/docs/a/b.txt"), "/docs/a");
+        assert_eq!(top_level_of("/docs", "/docs/b.txt"), "(root)");
+    }
+}
@@ -1,29 +1,108 @@
-use crate::utils::format_bytes;
-use cloudreve_api::api::v4::models::{FileType, ListFilesRequest};
-use cloudreve_api::{CloudreveClient, Result};
+use super::{delete, download, move_cmd, share};
+use crate::utils::remote::{self, RemoteFile};
+use crate::utils::{format_bytes, walk};
+use chrono::{DateTime, Utc};
+use cloudreve_api::api::v4::models::FileType;
+use cloudreve_api::{CloudreveAPI, CloudreveClient, Error, Result, UnifiedClient};
 use log::{error, info};
+use regex::Regex;
+use std::io::{self, Write};
 
 /// Search filter options
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct SearchFilter {
     pub name_pattern: Option<String>,
+    /// Regular expression matched against the file name
+    pub regex: Option<Regex>,
     pub file_type: Option<FileType>,
     pub min_size: Option<i64>,
     pub max_size: Option<i64>,
     pub extension: Option<String>,
+    /// image, video, audio or document (V4)
+    pub category: Option<String>,
+    /// Metadata key/value pairs that must all match, empty value = key present (V4)
+    pub metadata: Vec<(String, String)>,
+    /// Tags that must all be present (V4)
+    pub tags: Vec<String>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub modified_after: Option<DateTime<Utc>>,
+    pub modified_before: Option<DateTime<Utc>>,
+}
+
+impl SearchFilter {
+    /// Whether the filter uses conditions only the V4 search endpoint evaluates
+    fn needs_server(&self) -> bool {
+        self.category.is_some()
+            || !self.metadata.is_empty()
+            || !self.tags.is_empty()
+            || self.created_after.is_some()
+            || self.created_before.is_some()
+            || self.modified_after.is_some()
+            || self.modified_before.is_some()
+    }
+}
+
+/// How search results are printed
+#[derive(Debug, Clone, Copy, Default)]
+pub enum SearchOutput {
+    #[default]
+    Pretty,
+    /// One full path per line
+    Paths,
+    /// Full paths separated by NUL, for `xargs -0`
+    Print0,
+}
+
+/// Action run on every match, like `find -exec`
+#[derive(Debug, Clone)]
+pub struct SearchExec {
+    /// download, delete, move or share
+    pub action: String,
+    /// Output directory for download, destination folder for move
+    pub target: Option<String>,
+    /// Skip the confirmation of destructive actions
+    pub force: bool,
+    /// Let delete remove matched folders together with their contents
+    pub recursive: bool,
+}
+
+/// A search result
+struct SearchHit {
+    path: String,
+    name: String,
+    is_folder: bool,
+    size: i64,
+}
+
+impl From<RemoteFile> for SearchHit {
+    fn from(file: RemoteFile) -> Self {
+        SearchHit {
+            path: file.display_path(),
+            is_folder: file.is_folder(),
+            size: file.size,
+            name: file.name,
+        }
+    }
 }
 
 /// Handle file search
 pub async fn handle_search(
-    client: &CloudreveClient,
+    api: &CloudreveAPI,
+    http_client: &reqwest::Client,
     path: String,
     filter: SearchFilter,
     recursive: bool,
+    output: SearchOutput,
+    exec: Option<SearchExec>,
 ) -> Result<()> {
     info!("Searching in path: {}", path);
     if let Some(ref pattern) = filter.name_pattern {
         info!("Name pattern: {}", pattern);
     }
+    if let Some(ref regex) = filter.regex {
+        info!("Regex: {}", regex);
+    }
     if let Some(ref ext) = filter.extension {
         info!("Extension: {}", ext);
     }
@@ -36,103 +115,261 @@ pub async fn handle_search(
     if let Some(ref ft) = filter.file_type {
         info!("Type: {:?}", ft);
     }
+    if let Some(ref category) = filter.category {
+        info!("Category: {}", category);
+    }
     info!("Recursive: {}", recursive);
 
-    let mut results = Vec::new();
-    search_recursive(client, &path, &filter, recursive, &mut results).await?;
+    let results = match api.inner() {
+        UnifiedClient::V4(client) => search_server(client, &path, &filter, recursive).await?,
+        UnifiedClient::V3(_) => {
+            if filter.needs_server() {
+                return Err(Error::InvalidResponse(
+                    "Category, metadata, tag and date filters require a V4 server".to_string(),
+                ));
+            }
+            search_local(api, &path, &filter, recursive).await?
+        }
+    };
 
     if results.is_empty() {
         info!("No matching files found");
         return Ok(());
     }
 
-    info!("");
-    info!("Found {} matching items:", results.len());
-    for file in &results {
-        match file.r#type {
-            FileType::Folder => info!("  📁 {}/", file.name),
-            FileType::File => info!("  📄 {} ({})", file.name, format_bytes(file.size)),
+    match output {
+        SearchOutput::Pretty => {
+            info!("");
+            info!("Found {} matching items:", results.len());
+            for hit in &results {
+                if hit.is_folder {
+                    info!("  📁 {}/", hit.path);
+                } else {
+                    info!("  📄 {} ({})", hit.path, format_bytes(hit.size));
+                }
+            }
+        }
+        SearchOutput::Paths => {
+            for hit in &results {
+                println!("{}", hit.path);
+            }
+        }
+        SearchOutput::Print0 => {
+            let mut stdout = io::stdout().lock();
+            for hit in &results {
+                write!(stdout, "{}\0", hit.path)?;
+            }
+            stdout.flush()?;
         }
     }
 
-    Ok(())
+    match exec {
+        Some(exec) => run_exec(api, http_client, &results, exec).await,
+        None => Ok(()),
+    }
+}
+
+/// Runs the `--exec` action on all matches
+async fn run_exec(
+    api: &CloudreveAPI,
+    http_client: &reqwest::Client,
+    results: &[SearchHit],
+    exec: SearchExec,
+) -> Result<()> {
+    let paths: Vec<String> = results.iter().map(|hit| hit.path.clone()).collect();
+    info!("Running {} on {} match(es)", exec.action, paths.len());
+
+    match exec.action.as_str() {
+        "download" => {
+            let files: Vec<String> = results
+                .iter()
+                .filter(|hit| !hit.is_folder)
+                .map(|hit| hit.path.clone())
+                .collect();
+            let output = exec.target.unwrap_or_else(|| ".".to_string());
+            std::fs::create_dir_all(&output)?;
+            // Matched names are used as-is so `*` or `?` in them are not re-expanded
+            download::download_paths(api, http_client, files, output, None, 5).await
+        }
+        "delete" => {
+            delete::delete_paths(api, paths, exec.force, exec.recursive, false, false).await
+        }
+        "move" => {
+            let dest = exec.target.ok_or_else(|| {
+                Error::InvalidResponse("--exec move requires --exec-target <folder>".to_string())
+            })?;
+            move_cmd::move_paths(api, &paths, &dest).await
+        }
+        "share" => {
+            let mut failed = 0;
+            for path in paths {
+                if let Err(e) =
+                    share::handle_share(api, path.clone(), None, None, None, Default::default())
+                        .await
+                {
+                    failed += 1;
+                    error!("Failed to share {}: {}", path, e);
+                }
+            }
+            if failed > 0 {
+                error!("{} share(s) failed", failed);
+            }
+            Ok(())
+        }
+        other => Err(Error::InvalidResponse(format!(
+            "Unknown --exec action: {} (use download, delete, move or share)",
+            other
+        ))),
+    }
 }
 
-async fn search_recursive(
+/// Searches with the V4 search endpoint, which is always recursive
+async fn search_server(
     client: &CloudreveClient,
     path: &str,
     filter: &SearchFilter,
     recursive: bool,
-    results: &mut Vec<cloudreve_api::api::v4::models::File>,
-) -> Result<()> {
-    let request = ListFilesRequest {
-        path,
-        page: Some(0),
-        page_size: Some(100),
-        ..Default::default()
-    };
+) -> Result<Vec<SearchHit>> {
+    let uri = format!("{}?{}", remote::to_uri(path), search_query(filter));
+    let files = remote::list_all(client, &uri, 100).await?;
 
-    match client.list_files(&request).await {
-        Ok(response) => {
-            for file in response.files {
-                if matches_filter(&file, filter) {
-                    results.push(file.clone());
-                }
+    let root = walk::normalize_path(path);
+    Ok(files
+        .into_iter()
+        .map(SearchHit::from)
+        // Extension, regex and direct-children checks are not expressible server-side
+        .filter(|hit| matches_extension(&hit.name, filter))
+        .filter(|hit| matches_regex(&hit.name, filter))
+        .filter(|hit| recursive || parent_of(&hit.path) == root)
+        .collect())
+}
 
-                if recursive && file.r#type == FileType::Folder {
-                    let sub_path = format!("{}/{}/", path.trim_end_matches('/'), file.name);
-                    // Use Box::pin for recursive async call
-                    let search_future = Box::pin(search_recursive(
-                        client, &sub_path, filter, recursive, results,
-                    ));
-                    let _ = search_future.await;
-                }
-            }
+/// Builds the search query appended to the folder URI
+fn search_query(filter: &SearchFilter) -> String {
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+
+    if let Some(name) = &filter.name_pattern {
+        query.append_pair("name", name);
+    }
+    // Name terms are combined with AND, so the extension narrows the results
+    if let Some(ext) = &filter.extension {
+        query.append_pair("name", &format!(".{}", ext.trim_start_matches('.')));
+    }
+    if filter.name_pattern.is_some() || filter.extension.is_some() {
+        query.append_pair("case_folding", "true");
+    }
+
+    match filter.file_type {
+        Some(FileType::File) => {
+            query.append_pair("type", "file");
+        }
+        Some(FileType::Folder) => {
+            query.append_pair("type", "folder");
         }
-        Err(e) => {
-            error!("Error searching in {}: {}", path, e);
+        None => {}
+    }
+
+    if let Some(category) = &filter.category {
+        query.append_pair("category", category);
+    }
+    if let Some(min) = filter.min_size {
+        query.append_pair("size_gte", &min.to_string());
+    }
+    if let Some(max) = filter.max_size {
+        query.append_pair("size_lte", &max.to_string());
+    }
+
+    for (key, value) in &filter.metadata {
+        query.append_pair(&format!("meta_{}", key), value);
+    }
+    for tag in &filter.tags {
+        query.append_pair(&format!("meta_tag:{}", tag), "");
+    }
+
+    let times = [
+        ("created_gte", filter.created_after),
+        ("created_lte", filter.created_before),
+        ("updated_gte", filter.modified_after),
+        ("updated_lte", filter.modified_before),
+    ];
+    for (key, time) in times {
+        if let Some(time) = time {
+            query.append_pair(key, &time.timestamp().to_string());
         }
     }
 
-    Ok(())
+    query.finish()
+}
+
+/// Lists folders and filters locally (V3 servers have no search endpoint)
+async fn search_local(
+    api: &CloudreveAPI,
+    path: &str,
+    filter: &SearchFilter,
+    recursive: bool,
+) -> Result<Vec<SearchHit>> {
+    let max_depth = if recursive { None } else { Some(1) };
+    let nodes = walk::walk(api, path, max_depth, 5).await?;
+
+    Ok(nodes
+        .into_iter()
+        .map(|node| SearchHit {
+            path: node.path,
+            name: node.name,
+            is_folder: node.is_folder,
+            size: node.size,
+        })
+        .filter(|hit| matches_filter(hit, filter))
+        .collect())
 }
 
-fn matches_filter(file: &cloudreve_api::api::v4::models::File, filter: &SearchFilter) -> bool {
+fn matches_filter(hit: &SearchHit, filter: &SearchFilter) -> bool {
     // Name pattern filter (case-insensitive substring)
     if let Some(pattern) = &filter.name_pattern
-        && !file.name.to_lowercase().contains(&pattern.to_lowercase())
+        && !hit.name.to_lowercase().contains(&pattern.to_lowercase())
     {
         return false;
     }
 
     // Type filter
-    if let Some(ft) = &filter.file_type
-        && &file.r#type != ft
-    {
-        return false;
+    match filter.file_type {
+        Some(FileType::File) if hit.is_folder => return false,
+        Some(FileType::Folder) if !hit.is_folder => return false,
+        _ => {}
     }
 
     // Size filter
     if let Some(min) = filter.min_size
-        && file.size < min
+        && hit.size < min
     {
         return false;
     }
     if let Some(max) = filter.max_size
-        && file.size > max
+        && hit.size > max
     {
         return false;
     }
 
-    // Extension filter (case-insensitive)
-    if let Some(ext) = &filter.extension
-        && !file
-            .name
+    matches_extension(&hit.name, filter) && matches_regex(&hit.name, filter)
+}
+
+fn matches_regex(name: &str, filter: &SearchFilter) -> bool {
+    filter.regex.as_ref().is_none_or(|re| re.is_match(name))
+}
+
+/// Extension filter (case-insensitive)
+fn matches_extension(name: &str, filter: &SearchFilter) -> bool {
+    match &filter.extension {
+        Some(ext) => name
             .to_lowercase()
-            .ends_with(&format!(".{}", ext.to_lowercase()))
-    {
-        return false;
+            .ends_with(&format!(".{}", ext.trim_start_matches('.').to_lowercase())),
+        None => true,
     }
+}
 
-    true
+fn parent_of(path: &str) -> &str {
+    match path.rfind('/') {
+        Some(0) | None => "/",
+        Some(pos) => &path[..pos],
+    }
 }
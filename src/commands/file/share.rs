@@ -1,13 +1,29 @@
-use cloudreve_api::{CloudreveAPI, Result};
-use log::{error, info};
+use crate::commands::share::{ShareOptions, create};
+use crate::utils::remote;
+use cloudreve_api::{CloudreveAPI, Result, UnifiedClient};
+use log::{error, info, warn};
 
+/// Creates a share link for a path and returns its URL
 pub async fn handle_share(
     api: &CloudreveAPI,
     uri: String,
-    _name: Option<String>, // Unused due to API structure
+    name: Option<String>,
     expire: Option<u32>,
     password: Option<String>,
-) -> Result<()> {
+    options: ShareOptions,
+) -> Result<String> {
+    if let UnifiedClient::V4(client) = api.inner() {
+        return create::handle_create(
+            client,
+            remote::to_uri(&uri),
+            name,
+            expire,
+            password,
+            options,
+        )
+        .await;
+    }
+
     // Validate password format if provided
     if let Some(ref pwd) = password {
         if !pwd.chars().all(|c| c.is_alphanumeric()) {
@@ -24,24 +40,35 @@ pub async fn handle_share(
         }
     }
 
+    if options.downloads.is_some()
+        || options.show_readme.is_some()
+        || options.share_view.is_some()
+        || options.price.is_some()
+        || options.permission.is_some()
+        || options.permissions_json.is_some()
+    {
+        warn!(
+            "Download limit, readme, share view, price and permission options require a V4 server"
+        );
+    }
+
     info!("Creating share link for URI: {}", uri);
     if password.is_some() {
         info!("Password protected share");
     }
 
     match api
-        .create_share(&uri, _name.as_deref(), expire, password.as_deref())
+        .create_share(&uri, name.as_deref(), expire, password.as_deref())
         .await
     {
         Ok(share_url) => {
             info!("Share link created successfully!");
             info!("URL: {}", share_url);
+            Ok(share_url)
         }
         Err(e) => {
             error!("Error creating share link: {}", e);
-            return Err(e);
+            Err(e)
         }
     }
-
-    Ok(())
 }
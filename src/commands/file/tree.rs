@@ -0,0 +1,78 @@
+use crate::utils::format_bytes;
+use crate::utils::walk::{self, RemoteNode};
+use cloudreve_api::{CloudreveAPI, Result};
+use log::info;
+use std::collections::HashMap;
+
+pub async fn handle_tree(
+    api: &CloudreveAPI,
+    path: String,
+    depth: Option<usize>,
+    dirs_only: bool,
+    show_size: bool,
+    concurrency: usize,
+) -> Result<()> {
+    let root = walk::normalize_path(&path);
+    let nodes = walk::walk(api, &root, depth, concurrency).await?;
+
+    // Group children by parent folder
+    let mut children: HashMap<&str, Vec<&RemoteNode>> = HashMap::new();
+    for node in &nodes {
+        if dirs_only && !node.is_folder {
+            continue;
+        }
+        children.entry(node.parent()).or_default().push(node);
+    }
+    for list in children.values_mut() {
+        list.sort_by(|a, b| b.is_folder.cmp(&a.is_folder).then(a.name.cmp(&b.name)));
+    }
+
+    println!("{}", root);
+    print_children(&children, &root, "", show_size);
+
+    let folders = nodes.iter().filter(|n| n.is_folder).count();
+    let files = nodes.len() - folders;
+    info!("");
+    if dirs_only {
+        info!("{} directories", folders);
+    } else {
+        info!("{} directories, {} files", folders, files);
+    }
+
+    Ok(())
+}
+
+fn print_children(
+    children: &HashMap<&str, Vec<&RemoteNode>>,
+    dir: &str,
+    prefix: &str,
+    show_size: bool,
+) {
+    let Some(list) = children.get(dir) else {
+        return;
+    };
+
+    for (i, node) in list.iter().enumerate() {
+        let last = i + 1 == list.len();
+        let branch = if last { "└── " } else { "├── " };
+
+        if node.is_folder {
+            println!("{}{}{}/", prefix, branch, node.name);
+        } else if show_size {
+            println!(
+                "{}{}{} ({})",
+                prefix,
+                branch,
+                node.name,
+                format_bytes(node.size)
+            );
+        } else {
+            println!("{}{}{}", prefix, branch, node.name);
+        }
+
+        if node.is_folder {
+            let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            print_children(children, &node.path, &child_prefix, show_size);
+        }
+    }
+}
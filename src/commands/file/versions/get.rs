@@ -0,0 +1,58 @@
+use super::{fetch_versions, find_version};
+use crate::commands::file::download;
+use crate::utils::{envelope, format_bytes, remote};
+use cloudreve_api::{CloudreveClient, Error, Result};
+use log::info;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::path::Path;
+
+pub async fn handle_get(
+    client: &CloudreveClient,
+    http_client: &Client,
+    path: String,
+    version: String,
+    output: Option<String>,
+) -> Result<()> {
+    let (file, versions) = fetch_versions(client, &path).await?;
+    let entity = find_version(&versions, &version)?;
+
+    let output_path = match output {
+        Some(output) if output.ends_with('/') || Path::new(&output).is_dir() => {
+            format!(
+                "{}/{}",
+                output.trim_end_matches('/'),
+                version_file_name(&file.name, &entity.id)
+            )
+        }
+        Some(output) => output,
+        None => version_file_name(&file.name, &entity.id),
+    };
+
+    info!("Downloading version {} of {}", entity.id, path);
+
+    let request = json!({
+        "uris": [remote::to_uri(&path)],
+        "entity": entity.id,
+        "download": true,
+    });
+    let response: Value = envelope::data(client.post("/file/url", &request).await?)?;
+    let url = response
+        .pointer("/urls/0/url")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| Error::InvalidResponse("No download URL returned".to_string()))?;
+
+    let size = download::save_url(http_client, url, &output_path).await?;
+
+    info!("Saved to: {}", output_path);
+    info!("Size: {}", format_bytes(size as i64));
+    Ok(())
+}
+
+/// `report.pdf` + `abc` -> `report.abc.pdf`
+fn version_file_name(name: &str, version: &str) -> String {
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => format!("{}.{}.{}", stem, version, ext),
+        _ => format!("{}.{}", name, version),
+    }
+}
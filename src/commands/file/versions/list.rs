@@ -0,0 +1,44 @@
+use super::{fetch_versions, is_current};
+use crate::utils::{format_bytes, time};
+use cloudreve_api::{CloudreveClient, Result};
+use log::info;
+
+pub async fn handle_list(client: &CloudreveClient, path: String) -> Result<()> {
+    info!("Listing versions of: {}", path);
+
+    let (file, versions) = fetch_versions(client, &path).await?;
+
+    if versions.is_empty() {
+        info!("No versions found");
+        return Ok(());
+    }
+
+    info!("📄 {} ({} version(s))", file.name, versions.len());
+    for version in &versions {
+        let created = time::parse_remote_time(&version.created_at)
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|| version.created_at.clone());
+        let marker = if is_current(&file, version) {
+            " (current)"
+        } else {
+            ""
+        };
+
+        info!("");
+        info!("  🕒 {}{}", version.id, marker);
+        info!("     Created: {}", created);
+        info!("     Size:    {}", format_bytes(version.size));
+        if let Some(creator) = version.creator_name() {
+            info!("     By:      {}", creator);
+        }
+        if let Some(policy) = version.policy_name() {
+            info!("     Policy:  {}", policy);
+        }
+    }
+
+    let total: i64 = versions.iter().map(|v| v.size).sum();
+    info!("");
+    info!("Total: {} across all versions", format_bytes(total));
+
+    Ok(())
+}
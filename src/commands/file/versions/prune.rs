@@ -0,0 +1,96 @@
+use super::{VersionRequest, fetch_versions, is_current};
+use crate::utils::{envelope, format_bytes, remote};
+use cloudreve_api::{CloudreveClient, Error, Result};
+use log::{error, info};
+use std::io::{self, Write};
+
+pub async fn handle_prune(
+    client: &CloudreveClient,
+    path: String,
+    keep: usize,
+    dry_run: bool,
+    force: bool,
+) -> Result<()> {
+    if keep == 0 {
+        return Err(Error::InvalidResponse(
+            "--keep must be at least 1, the current version cannot be deleted".to_string(),
+        ));
+    }
+
+    let (file, versions) = fetch_versions(client, &path).await?;
+
+    // The current version always counts towards the kept ones
+    let mut kept = 0;
+    let mut to_delete = Vec::new();
+    for version in &versions {
+        if is_current(&file, version) {
+            continue;
+        }
+        if kept + 1 < keep {
+            kept += 1;
+        } else {
+            to_delete.push(version);
+        }
+    }
+
+    if to_delete.is_empty() {
+        info!(
+            "Nothing to prune: {} has {} version(s)",
+            path,
+            versions.len()
+        );
+        return Ok(());
+    }
+
+    let freed: i64 = to_delete.iter().map(|v| v.size).sum();
+    info!(
+        "Versions to delete: {} ({})",
+        to_delete.len(),
+        format_bytes(freed)
+    );
+    for version in &to_delete {
+        info!("  - {} ({})", version.id, version.created_at);
+    }
+
+    if dry_run {
+        info!("Dry run, nothing deleted");
+        return Ok(());
+    }
+
+    if !force {
+        print!("Delete {} version(s)? [y/N]: ", to_delete.len());
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            info!("Operation cancelled");
+            return Ok(());
+        }
+    }
+
+    let uri = remote::to_uri(&path);
+    let mut failed = 0;
+    for version in &to_delete {
+        let request = VersionRequest {
+            uri: &uri,
+            version: &version.id,
+        };
+        if let Err(e) = client
+            .delete_with_body("/file/version", &request)
+            .await
+            .and_then(envelope::check)
+        {
+            failed += 1;
+            error!("Failed to delete version {}: {}", version.id, e);
+        }
+    }
+
+    info!(
+        "Prune complete: {} deleted, {} failed",
+        to_delete.len() - failed,
+        failed
+    );
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use super::{VersionRequest, fetch_versions, find_version, is_current};
+use crate::utils::{envelope, remote};
+use cloudreve_api::{CloudreveClient, Result};
+use log::info;
+
+pub async fn handle_restore(client: &CloudreveClient, path: String, version: String) -> Result<()> {
+    let (file, versions) = fetch_versions(client, &path).await?;
+    let entity = find_version(&versions, &version)?;
+
+    if is_current(&file, entity) {
+        info!("Version {} is already the current version", entity.id);
+        return Ok(());
+    }
+
+    info!("Restoring {} to version {}", path, entity.id);
+
+    let uri = remote::to_uri(&path);
+    let request = VersionRequest {
+        uri: &uri,
+        version: &entity.id,
+    };
+    envelope::check(client.put("/file/version/current", &request).await?)?;
+
+    info!("✅ Version {} is now current", entity.id);
+    Ok(())
+}
@@ -1,11 +1,13 @@
 // 命令模块的导出文件
 
 pub mod auth;
+pub mod config;
 pub mod dav;
 pub mod file;
 pub mod settings;
 pub mod share;
 pub mod site;
+pub mod trash;
 pub mod user;
 pub mod version;
 pub mod workflow;
@@ -0,0 +1,55 @@
+use super::schema::{Setting, V3_SETTINGS, V4_SETTINGS};
+use crate::utils::envelope;
+use cloudreve_api::{CloudreveAPI, Result, UnifiedClient};
+use log::{debug, info};
+use serde_json::{Map, Value};
+
+/// Prints portable settings as a JSON object for `settings import`
+pub async fn handle_export(api: &CloudreveAPI) -> Result<()> {
+    info!("Exporting user settings...");
+
+    let values = match api.inner() {
+        UnifiedClient::V4(client) => {
+            let mut source: Value = envelope::data(client.get("/user/setting").await?)?;
+            // Language and theme are part of the user profile rather than the settings
+            match client.get("/user/me").await.and_then(envelope::data) {
+                Ok(Value::Object(profile)) => {
+                    if let Value::Object(settings) = &mut source {
+                        for (key, value) in profile {
+                            settings.entry(key).or_insert(value);
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => debug!("Could not read user profile: {}", e),
+            }
+            collect(V4_SETTINGS, &source, |key| key)
+        }
+        UnifiedClient::V3(client) => {
+            let source: Value = envelope::data(client.get("/user/setting").await?)?;
+            collect(V3_SETTINGS, &source, |key| match key {
+                "theme" => "prefer_theme",
+                other => other,
+            })
+        }
+    };
+
+    println!("{}", serde_json::to_string_pretty(&Value::Object(values))?);
+    Ok(())
+}
+
+/// Picks portable settings out of a server response
+fn collect(
+    table: &[Setting],
+    source: &Value,
+    field: impl Fn(&'static str) -> &'static str,
+) -> Map<String, Value> {
+    table
+        .iter()
+        .filter(|s| s.portable)
+        .filter_map(|s| {
+            let value = source.get(field(s.key))?;
+            (!value.is_null()).then(|| (s.key.to_string(), value.clone()))
+        })
+        .collect()
+}
@@ -0,0 +1,58 @@
+use super::schema::{self, V3_SETTINGS, V4_SETTINGS};
+use super::{set, v3};
+use cloudreve_api::{CloudreveAPI, Result, UnifiedClient};
+use log::{error, info, warn};
+use serde_json::Value;
+use std::fs;
+
+/// Applies a file written by `settings export`
+pub async fn handle_import(api: &CloudreveAPI, file: String, dry_run: bool) -> Result<()> {
+    let document: Value = serde_json::from_str(&fs::read_to_string(&file)?)?;
+    let (table, other, version) = match api.inner() {
+        UnifiedClient::V4(_) => (V4_SETTINGS, V3_SETTINGS, "V4"),
+        UnifiedClient::V3(_) => (V3_SETTINGS, V4_SETTINGS, "V3"),
+    };
+    let (values, unknown) = schema::parse_document(table, document)?;
+    for key in &unknown {
+        if schema::lookup(other, key).is_ok() {
+            warn!("Skipping {}: not supported on {} servers", key, version);
+        } else {
+            warn!("Skipping unknown setting: {}", key);
+        }
+    }
+
+    if values.is_empty() {
+        info!("No settings in {}", file);
+        return Ok(());
+    }
+
+    info!("Importing {} setting(s) from {}:", values.len(), file);
+    for (key, value) in &values {
+        info!("  {} = {}", key, value);
+    }
+    if dry_run {
+        info!("Dry run, nothing changed");
+        return Ok(());
+    }
+
+    match api.inner() {
+        UnifiedClient::V4(client) => set::apply(client, values).await?,
+        UnifiedClient::V3(_) => {
+            // V3 applies one key per request, so a failure can leave a partial import
+            let mut applied = Vec::new();
+            for (key, value) in values {
+                if let Err(e) = v3::update(api, &key, value).await {
+                    error!("Failed to import {}: {}", key, e);
+                    if !applied.is_empty() {
+                        error!("Already applied: {}", applied.join(", "));
+                    }
+                    return Err(e);
+                }
+                applied.push(key);
+            }
+        }
+    }
+
+    info!("Settings imported successfully");
+    Ok(())
+}
@@ -1,5 +1,9 @@
+pub mod export;
 pub mod get;
+pub mod import;
+pub mod schema;
 pub mod set;
+pub mod v3;
 
 use cloudreve_api::{CloudreveAPI, Result, UnifiedClient};
 
@@ -12,27 +16,57 @@ pub enum SettingsCommands {
         key: Option<String>,
     },
 
-    /// Set a setting value
+    /// Set one or more settings (`settings set theme=#3f51b5 language=en-US`)
     Set {
+        /// Settings as key=value pairs
+        #[clap(required_unless_present = "key", conflicts_with = "key")]
+        assignments: Vec<String>,
+
         /// Setting key
-        #[clap(short, long)]
-        key: String,
+        #[clap(short, long, requires = "value")]
+        key: Option<String>,
 
-        /// Setting value (JSON)
-        #[clap(short, long)]
-        value: String,
+        /// Setting value (bool, integer, text, or a JSON array / comma-separated list)
+        #[clap(short, long, requires = "key")]
+        value: Option<String>,
+    },
+
+    /// Print portable settings as JSON (`settings export > settings.json`)
+    Export,
+
+    /// Apply settings from a file written by `settings export`
+    Import {
+        /// Settings JSON file
+        file: String,
+
+        /// Show what would change without applying it
+        #[clap(long)]
+        dry_run: bool,
     },
 }
 
 pub async fn handle_settings_command(api: &CloudreveAPI, command: SettingsCommands) -> Result<()> {
-    // For now, use V4 client through inner()
-    match api.inner() {
-        UnifiedClient::V4(client) => match command {
-            SettingsCommands::Get { key } => get::handle_get(client, key).await,
-            SettingsCommands::Set { key, value } => set::handle_set(client, key, value).await,
+    match command {
+        SettingsCommands::Get { key } => match api.inner() {
+            UnifiedClient::V4(client) => get::handle_get(client, key).await,
+            UnifiedClient::V3(_) => v3::handle_get(api, key).await,
         },
-        UnifiedClient::V3(_) => Err(cloudreve_api::Error::InvalidResponse(
-            "Settings commands not yet supported for V3 API".to_string(),
-        )),
+        SettingsCommands::Set {
+            mut assignments,
+            key,
+            value,
+        } => {
+            if let (Some(key), Some(value)) = (key, value) {
+                assignments.push(format!("{}={}", key, value));
+            }
+            match api.inner() {
+                UnifiedClient::V4(client) => set::handle_set(client, assignments).await,
+                UnifiedClient::V3(_) => v3::handle_set(api, assignments).await,
+            }
+        }
+        SettingsCommands::Export => export::handle_export(api).await,
+        SettingsCommands::Import { file, dry_run } => {
+            import::handle_import(api, file, dry_run).await
+        }
     }
 }
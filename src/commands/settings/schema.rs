@@ -0,0 +1,233 @@
+//! Writable user settings and their value types
+//!
+//! `settings set`, `settings import` and `settings export` all go through
+//! these tables, so adding a key here is enough to support it everywhere.
+
+use cloudreve_api::{Error, Result};
+use serde_json::{Map, Value};
+
+/// Value type of a setting
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Text,
+    Bool,
+    Integer,
+    /// JSON array or comma-separated list of strings
+    TextList,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Text => "string",
+            Kind::Bool => "bool",
+            Kind::Integer => "integer",
+            Kind::TextList => "list",
+        }
+    }
+}
+
+/// One writable setting
+#[derive(Debug)]
+pub struct Setting {
+    /// Canonical key, also the field name sent to the server
+    pub key: &'static str,
+    pub aliases: &'static [&'static str],
+    pub kind: Kind,
+    /// Included in `settings export`; account-specific keys are not
+    pub portable: bool,
+}
+
+const fn setting(
+    key: &'static str,
+    aliases: &'static [&'static str],
+    kind: Kind,
+    portable: bool,
+) -> Setting {
+    Setting {
+        key,
+        aliases,
+        kind,
+        portable,
+    }
+}
+
+/// Settings accepted by `PATCH /user/setting` on V4
+pub const V4_SETTINGS: &[Setting] = &[
+    setting("nick", &["nickname"], Kind::Text, false),
+    setting("language", &[], Kind::Text, true),
+    setting("preferred_theme", &["theme"], Kind::Text, true),
+    setting("version_retention_enabled", &[], Kind::Bool, true),
+    setting("version_retention_max", &[], Kind::Integer, true),
+    setting("version_retention_ext", &[], Kind::TextList, true),
+    setting("disable_view_sync", &[], Kind::Bool, true),
+];
+
+/// Settings V3 can change, one `PATCH /user/setting/<key>` each
+pub const V3_SETTINGS: &[Setting] = &[
+    setting("nick", &["nickname"], Kind::Text, false),
+    setting("theme", &["preferred_theme"], Kind::Text, true),
+    setting("homepage", &[], Kind::Bool, true),
+];
+
+/// Finds a setting by key or alias
+pub fn lookup<'a>(table: &'a [Setting], key: &str) -> Result<&'a Setting> {
+    table
+        .iter()
+        .find(|s| s.key == key || s.aliases.contains(&key))
+        .ok_or_else(|| {
+            Error::InvalidResponse(format!(
+                "Unknown setting key: {}. Available keys: {}",
+                key,
+                describe(table)
+            ))
+        })
+}
+
+/// `key (type), ...` listing for error messages
+pub fn describe(table: &[Setting]) -> String {
+    table
+        .iter()
+        .map(|s| format!("{} ({})", s.key, s.kind.name()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parses a command line value into the setting's type
+pub fn parse_value(setting: &Setting, raw: &str) -> Result<Value> {
+    let invalid = || {
+        Error::InvalidResponse(format!(
+            "Invalid value for {}: expected {}, got '{}'",
+            setting.key,
+            setting.kind.name(),
+            raw
+        ))
+    };
+
+    match setting.kind {
+        Kind::Text => Ok(Value::String(raw.to_string())),
+        Kind::Bool => raw.parse::<bool>().map(Value::Bool).map_err(|_| invalid()),
+        Kind::Integer => raw
+            .parse::<i64>()
+            .map(|n| Value::Number(n.into()))
+            .map_err(|_| invalid()),
+        Kind::TextList if raw.trim_start().starts_with('[') => {
+            let value: Value = serde_json::from_str(raw).map_err(|_| invalid())?;
+            check_value(setting, value)
+        }
+        Kind::TextList => Ok(Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| Value::String(s.to_string()))
+                .collect(),
+        )),
+    }
+}
+
+/// Validates a JSON value (e.g. from an import file) against the setting's type
+pub fn check_value(setting: &Setting, value: Value) -> Result<Value> {
+    let valid = match (&value, setting.kind) {
+        (Value::String(s), kind) if kind != Kind::Text => return parse_value(setting, s),
+        (Value::String(_), Kind::Text) | (Value::Bool(_), Kind::Bool) => true,
+        (Value::Number(n), Kind::Integer) => n.is_i64(),
+        (Value::Array(items), Kind::TextList) => items.iter().all(Value::is_string),
+        _ => false,
+    };
+
+    if valid {
+        Ok(value)
+    } else {
+        Err(Error::InvalidResponse(format!(
+            "Invalid value for {}: expected {}, got {}",
+            setting.key,
+            setting.kind.name(),
+            value
+        )))
+    }
+}
+
+/// Parses `key=value` arguments into canonical keys and typed values
+pub fn parse_assignments(table: &[Setting], args: &[String]) -> Result<Map<String, Value>> {
+    let mut values = Map::new();
+    for arg in args {
+        let (key, raw) = arg
+            .split_once('=')
+            .ok_or_else(|| Error::InvalidResponse(format!("Expected key=value, got '{}'", arg)))?;
+        let setting = lookup(table, key.trim())?;
+        values.insert(setting.key.to_string(), parse_value(setting, raw)?);
+    }
+    Ok(values)
+}
+
+/// Validates an exported settings object, resolving aliases
+///
+/// Keys the table does not know (e.g. exported from the other server
+/// version) are returned separately instead of failing the whole file.
+pub fn parse_document(
+    table: &[Setting],
+    document: Value,
+) -> Result<(Map<String, Value>, Vec<String>)> {
+    let Value::Object(entries) = document else {
+        return Err(Error::InvalidResponse(
+            "Settings file must contain a JSON object".to_string(),
+        ));
+    };
+
+    let mut values = Map::new();
+    let mut unknown = Vec::new();
+    for (key, value) in entries {
+        match lookup(table, &key) {
+            Ok(setting) => {
+                values.insert(setting.key.to_string(), check_value(setting, value)?);
+            }
+            Err(_) => unknown.push(key),
+        }
+    }
+    Ok((values, unknown))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_assignments() {
+        let args = vec![
+            "theme=#3f51b5".to_string(),
+            "version_retention_max=10".to_string(),
+            "version_retention_ext=docx, xlsx".to_string(),
+            "disable_view_sync=true".to_string(),
+        ];
+        let values = parse_assignments(V4_SETTINGS, &args).unwrap();
+        // 别名解析为规范键名
+        assert_eq!(values["preferred_theme"], json!("#3f51b5"));
+        assert_eq!(values["version_retention_max"], json!(10));
+        assert_eq!(values["version_retention_ext"], json!(["docx", "xlsx"]));
+        assert_eq!(values["disable_view_sync"], json!(true));
+
+        // 类型不符或未知键报错
+        assert!(parse_assignments(V4_SETTINGS, &["disable_view_sync=yes".to_string()]).is_err());
+        assert!(parse_assignments(V4_SETTINGS, &["homepage=true".to_string()]).is_err());
+        assert!(parse_assignments(V4_SETTINGS, &["language".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_parse_document() {
+        let document = json!({
+            "theme": "#000000",
+            "version_retention_enabled": "false",
+            "version_retention_ext": ["pdf"],
+            "homepage": true,
+        });
+        let (values, unknown) = parse_document(V4_SETTINGS, document).unwrap();
+        assert_eq!(values["preferred_theme"], json!("#000000"));
+        assert_eq!(values["version_retention_enabled"], json!(false));
+        // V3 专有的键被跳过而不是报错
+        assert_eq!(unknown, vec!["homepage".to_string()]);
+
+        assert!(parse_document(V4_SETTINGS, json!({ "version_retention_max": "ten" })).is_err());
+        assert!(parse_document(V4_SETTINGS, json!(["language"])).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+//! User settings on V3 servers
+//!
+//! V3 exposes a smaller setting set and updates one option per request
+//! through `PATCH /user/setting/<option>`.
+
+use super::schema::{self, V3_SETTINGS, V4_SETTINGS};
+use crate::utils::envelope;
+use cloudreve_api::{CloudreveAPI, Error, Result, UnifiedClient};
+use log::info;
+use serde_json::{Value, json};
+
+/// Keys readable on V3
+const V3_KEYS: &str = "two_fa_enabled, theme, homepage, group_expires, passkeys";
+
+/// V4 keys without a V3 counterpart
+const V4_ONLY_KEYS: [&str; 7] = [
+    "credit",
+    "passwordless",
+    "version_retention",
+    "storage_packs",
+    "login_activity",
+    "language",
+    "disable_view_sync",
+];
+
+pub async fn handle_get(api: &CloudreveAPI, key: Option<String>) -> Result<()> {
+    let UnifiedClient::V3(client) = api.inner() else {
+        return Err(Error::InvalidResponse("V3 client required".to_string()));
+    };
+
+    info!("Getting user settings...");
+    let settings: Value = envelope::data(client.get("/user/setting").await?)?;
+    let passkeys = settings["authn"].as_array().cloned().unwrap_or_default();
+
+    let Some(k) = key else {
+        info!("User Settings:");
+        info!(
+            "  2FA Enabled: {}",
+            settings["two_factor"].as_bool().unwrap_or(false)
+        );
+        info!("  Theme: {}", text(&settings["prefer_theme"]));
+        info!(
+            "  Public Homepage: {}",
+            settings["homepage"].as_bool().unwrap_or(false)
+        );
+        if settings["group_expires"].as_i64().unwrap_or(0) > 0 {
+            info!("  Group Expires: {}", text(&settings["group_expires"]));
+        }
+        info!("  Passkeys: {}", passkeys.len());
+        return Ok(());
+    };
+
+    match k.as_str() {
+        "two_fa_enabled" => info!(
+            "2FA Enabled: {}",
+            settings["two_factor"].as_bool().unwrap_or(false)
+        ),
+        "theme" | "preferred_theme" => info!("Theme: {}", text(&settings["prefer_theme"])),
+        "homepage" => info!(
+            "Public Homepage: {}",
+            settings["homepage"].as_bool().unwrap_or(false)
+        ),
+        "group_expires" => info!("Group Expires: {}", text(&settings["group_expires"])),
+        "passkeys" => {
+            info!("Passkeys ({}):", passkeys.len());
+            for key in &passkeys {
+                info!("  - {}", text(&key["fingerprint"]));
+            }
+        }
+        other if V4_ONLY_KEYS.contains(&other) => {
+            return Err(Error::InvalidResponse(format!(
+                "Setting '{}' is not available on V3 servers. V3 keys: {}",
+                other, V3_KEYS
+            )));
+        }
+        other => {
+            info!("Setting '{}' not found. Available keys on V3:", other);
+            info!("  {}", V3_KEYS);
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn handle_set(api: &CloudreveAPI, assignments: Vec<String>) -> Result<()> {
+    let values =
+        schema::parse_assignments(V3_SETTINGS, &assignments).map_err(|e| {
+            match assignments.iter().find_map(|a| v4_only(a)) {
+                Some(key) => Error::InvalidResponse(format!(
+                    "Setting '{}' is not supported on V3 servers. V3 keys: {}",
+                    key,
+                    schema::describe(V3_SETTINGS)
+                )),
+                None => e,
+            }
+        })?;
+
+    for (key, value) in values {
+        info!("Updating setting: {} = {}", key, value);
+        update(api, &key, value).await?;
+    }
+
+    info!("Setting updated successfully");
+    Ok(())
+}
+
+/// Sends one validated setting, V3 takes each option on its own endpoint
+pub async fn update(api: &CloudreveAPI, key: &str, value: Value) -> Result<()> {
+    let UnifiedClient::V3(client) = api.inner() else {
+        return Err(Error::InvalidResponse("V3 client required".to_string()));
+    };
+
+    let request = match key {
+        "homepage" => json!({ "status": value }),
+        other => json!({ other: value }),
+    };
+    envelope::check(
+        client
+            .patch(&format!("/user/setting/{}", key), &request)
+            .await?,
+    )
+}
+
+/// Key of an assignment that only V4 servers understand
+fn v4_only(assignment: &str) -> Option<&str> {
+    let key = assignment
+        .split_once('=')
+        .map_or(assignment, |(k, _)| k)
+        .trim();
+    (schema::lookup(V3_SETTINGS, key).is_err() && schema::lookup(V4_SETTINGS, key).is_ok())
+        .then_some(key)
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
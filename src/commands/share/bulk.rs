@@ -0,0 +1,154 @@
+use super::ShareOptions;
+use crate::commands::file::share;
+use crate::utils::concurrency::execute_with_concurrency;
+use crate::utils::{csv_field, glob};
+use chrono::{Duration, Utc};
+use cloudreve_api::{CloudreveAPI, Error, Result};
+use log::{error, info};
+use rand::Rng;
+use rand::distr::Alphanumeric;
+use serde::Serialize;
+use std::fs;
+
+/// Input and output of a bulk creation
+pub struct BulkCreate {
+    /// File with one path or glob per line
+    pub from_file: String,
+    /// Manifest file, stdout when absent
+    pub manifest: Option<String>,
+    /// csv or json
+    pub manifest_format: Option<String>,
+    pub concurrency: usize,
+}
+
+/// One manifest row
+#[derive(Serialize)]
+struct ManifestEntry {
+    path: String,
+    url: String,
+    password: Option<String>,
+    expires: Option<String>,
+}
+
+/// Length of generated share passwords
+const PASSWORD_LENGTH: usize = 8;
+
+/// Random alphanumeric share password
+pub fn generate_password() -> String {
+    rand::rng()
+        .sample_iter(Alphanumeric)
+        .take(PASSWORD_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// Creates one share link per listed path and writes a manifest
+pub async fn handle_bulk_create(
+    api: &CloudreveAPI,
+    bulk: BulkCreate,
+    expire: Option<u32>,
+    password: Option<String>,
+    generate_passwords: bool,
+    options: ShareOptions,
+) -> Result<()> {
+    let content = fs::read_to_string(&bulk.from_file)?;
+    let patterns: Vec<String> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect();
+
+    // Globs expand to files only, plain paths may be folders
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        if glob::has_glob_pattern(&pattern) {
+            paths.extend(glob::expand_remote_patterns(api, &[pattern], false).await?);
+        } else {
+            paths.push(pattern);
+        }
+    }
+
+    if paths.is_empty() {
+        return Err(Error::InvalidResponse(format!(
+            "No paths to share in {}",
+            bulk.from_file
+        )));
+    }
+
+    info!("Creating {} share link(s)...", paths.len());
+    let expires = expire.map(|secs| (Utc::now() + Duration::seconds(secs as i64)).to_rfc3339());
+
+    let tasks: Vec<_> = paths
+        .into_iter()
+        .map(|path| {
+            let api = api.clone();
+            let options = options.clone();
+            let password = if generate_passwords {
+                Some(generate_password())
+            } else {
+                password.clone()
+            };
+            (path.clone(), async move {
+                let url = share::handle_share(&api, path, None, expire, password.clone(), options)
+                    .await?;
+                Ok::<_, Error>((url, password))
+            })
+        })
+        .collect();
+
+    let mut entries = Vec::new();
+    let mut failed = 0;
+    for (path, result) in execute_with_concurrency(tasks, bulk.concurrency).await {
+        match result {
+            Ok((url, password)) => entries.push(ManifestEntry {
+                path,
+                url,
+                password,
+                expires: expires.clone(),
+            }),
+            Err(e) => {
+                failed += 1;
+                error!("Failed to share {}: {}", path, e);
+            }
+        }
+    }
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let format = bulk.manifest_format.clone().unwrap_or_else(|| {
+        match bulk.manifest.as_deref() {
+            Some(file) if file.ends_with(".json") => "json",
+            _ => "csv",
+        }
+        .to_string()
+    });
+    let rendered = match format.as_str() {
+        "json" => serde_json::to_string_pretty(&entries)?,
+        _ => render_csv(&entries),
+    };
+
+    match &bulk.manifest {
+        Some(file) => {
+            fs::write(file, rendered)?;
+            info!("Manifest written to {}", file);
+        }
+        None => println!("{}", rendered),
+    }
+
+    info!("Created {} share link(s), {} failed", entries.len(), failed);
+    Ok(())
+}
+
+fn render_csv(entries: &[ManifestEntry]) -> String {
+    let mut csv = String::from("path,url,password,expires\n");
+    for entry in entries {
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            csv_field(&entry.path),
+            csv_field(&entry.url),
+            csv_field(entry.password.as_deref().unwrap_or_default()),
+            entry.expires.as_deref().unwrap_or_default()
+        ));
+    }
+    csv
+}
@@ -1,15 +1,18 @@
-use cloudreve_api::api::v4::models::{CreateShareLinkRequest, PermissionSetting};
+use super::ShareOptions;
+use crate::utils::envelope;
 use cloudreve_api::{CloudreveClient, Result};
 use log::{error, info};
 use serde_json::json;
 
+/// Creates a share link and returns its URL
 pub async fn handle_create(
     client: &CloudreveClient,
     uri: String,
-    _name: Option<String>,
+    name: Option<String>,
     expire: Option<u32>,
     password: Option<String>,
-) -> Result<()> {
+    options: ShareOptions,
+) -> Result<String> {
     // Validate password format if provided
     if let Some(ref pwd) = password {
         if !pwd.chars().all(|c| c.is_alphanumeric()) {
@@ -27,38 +30,45 @@ pub async fn handle_create(
     }
 
     // is_private must be true when password is set
-    let is_private = Some(password.is_some());
+    let is_private = password.is_some();
 
     info!("Creating share link for: {}", uri);
+    if let Some(ref name) = name {
+        info!("Name: {}", name);
+    }
     if password.is_some() {
         info!("Password protected share");
     }
+    if let Some(downloads) = options.downloads {
+        info!("Download limit: {}", downloads);
+    }
 
-    // Create permission setting with proper defaults
-    let permission = PermissionSetting {
-        user_explicit: json!({}),
-        group_explicit: json!({}),
-        same_group: "read".to_string(),
-        other: "none".to_string(),
-        anonymous: "none".to_string(),
-        everyone: "read".to_string(),
-    };
-
-    let request = CreateShareLinkRequest {
-        permissions: permission,
-        uri: uri.clone(),
-        is_private,
-        share_view: Some(true),
-        expire,
-        price: Some(0),
-        password,
-        show_readme: Some(true),
-    };
+    // Sent as raw JSON: the typed request has no name or download limit
+    let mut request = json!({
+        "permissions": options.permissions()?,
+        "uri": uri,
+        "is_private": is_private,
+        "share_view": options.share_view.unwrap_or(true),
+        "show_readme": options.show_readme.unwrap_or(true),
+        "price": options.price.unwrap_or(0),
+    });
+    if let Some(name) = name {
+        request["name"] = json!(name);
+    }
+    if let Some(expire) = expire {
+        request["expire"] = json!(expire);
+    }
+    if let Some(password) = password {
+        request["password"] = json!(password);
+    }
+    if let Some(downloads) = options.downloads {
+        request["downloads"] = json!(downloads);
+    }
 
-    let share_url = client.create_share_link(&request).await?;
+    let share_url: String = envelope::data(client.put("/share", &request).await?)?;
 
     info!("Share link created successfully:");
     info!("  URL: {}", share_url);
 
-    Ok(())
+    Ok(share_url)
 }
@@ -1,11 +1,74 @@
-use cloudreve_api::{CloudreveClient, Result};
-use log::info;
+use super::{ShareFilter, ShareItem, delete_share, select_shares};
+use cloudreve_api::{CloudreveAPI, Result};
+use log::{error, info};
+use std::io::{self, Write};
 
-pub async fn handle_delete(client: &CloudreveClient, id: String) -> Result<()> {
-    info!("Deleting share link: {}", id);
+pub async fn handle_delete(
+    api: &CloudreveAPI,
+    ids: Vec<String>,
+    filter: ShareFilter,
+    dry_run: bool,
+    force: bool,
+) -> Result<()> {
+    let shares = select_shares(api, &ids, &filter).await?;
+    // A plain delete by ID keeps the old, prompt-free behaviour
+    let confirm = !force && !filter.is_empty();
+    delete_shares(api, shares, dry_run, confirm).await
+}
+
+/// Deletes the given share links, listing them first
+pub async fn delete_shares(
+    api: &CloudreveAPI,
+    shares: Vec<ShareItem>,
+    dry_run: bool,
+    confirm: bool,
+) -> Result<()> {
+    if shares.is_empty() {
+        info!("No matching share links");
+        return Ok(());
+    }
+
+    info!("Share links to delete: {}", shares.len());
+    for share in &shares {
+        info!(
+            "  - {} {} ({})",
+            share.id,
+            share.name,
+            share.source_path().as_deref().unwrap_or("unknown source")
+        );
+    }
+
+    if dry_run {
+        info!("Dry run, nothing deleted");
+        return Ok(());
+    }
+
+    if confirm {
+        print!("Delete {} share link(s)? [y/N]: ", shares.len());
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            info!("Operation cancelled");
+            return Ok(());
+        }
+    }
 
-    client.delete_share_link(&id).await?;
+    let mut failed = 0;
+    for share in &shares {
+        info!("Deleting share link: {}", share.id);
+        if let Err(e) = delete_share(api, &share.id).await {
+            failed += 1;
+            error!("Failed to delete {}: {}", share.id, e);
+        }
+    }
 
-    info!("Share link deleted successfully");
+    info!(
+        "Deleted {} share link(s), {} failed",
+        shares.len() - failed,
+        failed
+    );
     Ok(())
 }
@@ -1,23 +1,53 @@
-use cloudreve_api::{CloudreveClient, Result};
+use super::{ShareFilter, fetch_shares, url_with_password};
+use crate::utils::qr::QrOptions;
+use cloudreve_api::{CloudreveAPI, Error, Result};
 use log::info;
 
 pub async fn handle_list(
-    client: &CloudreveClient,
+    api: &CloudreveAPI,
     page_size: Option<u32>,
-    _order_by: Option<String>,
+    order_by: Option<String>,
+    ids: Vec<String>,
+    filter: ShareFilter,
+    qr: QrOptions,
 ) -> Result<()> {
     info!("Listing share links...");
 
     let page_size = page_size.unwrap_or(50);
-    let (shares, _next_token) = client
-        .list_my_share_links_with_params(page_size, None, None, None)
-        .await?;
+    let mut shares = fetch_shares(api, &filter, order_by.as_deref(), page_size).await?;
+    if !ids.is_empty() {
+        shares.retain(|s| ids.contains(&s.id));
+    }
+
+    if qr.qr_file.is_some() && shares.len() != 1 {
+        return Err(Error::InvalidResponse(format!(
+            "--qr-file needs exactly one share link, {} matched (use --id)",
+            shares.len()
+        )));
+    }
 
     info!("Share links ({} total):", shares.len());
     for share in &shares {
         info!("  - ID: {}", share.id);
         info!("    Name: {}", share.name);
         info!("    URL: {}", share.url);
+        if let Some(path) = share.source_path() {
+            info!("    Source: {}", path);
+        }
+        info!("    Created: {}", share.created_at);
+        info!(
+            "    Expires: {}{}",
+            share.expires.as_deref().unwrap_or("never"),
+            if share.is_expired() { " (expired)" } else { "" }
+        );
+        if share.is_protected() {
+            info!("    Password protected");
+        }
+        info!(
+            "    Views: {}, downloads: {}",
+            share.visited, share.downloaded
+        );
+        qr.show(&url_with_password(&share.url, share.password.as_deref()))?;
     }
 
     Ok(())
@@ -1,9 +1,343 @@
+pub mod bulk;
 pub mod create;
 pub mod delete;
 pub mod list;
+pub mod prune;
+pub mod public;
+pub mod save;
 pub mod update;
+pub mod v3;
 
-use cloudreve_api::{CloudreveAPI, Result, UnifiedClient};
+use crate::commands::file::share as file_share;
+use crate::utils::credentials::{self, SecretSource};
+use crate::utils::qr::QrOptions;
+use crate::utils::{envelope, time};
+use chrono::{DateTime, Utc};
+use cloudreve_api::api::v4::uri::uri_to_path;
+use cloudreve_api::{CloudreveAPI, CloudreveClient, Error, Result, UnifiedClient};
+use log::{info, warn};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+/// Share link options beyond name, expiry and password
+#[derive(clap::Args, Clone, Debug, Default)]
+pub struct ShareOptions {
+    /// Expire the link after this many downloads
+    #[clap(long)]
+    pub downloads: Option<u32>,
+
+    /// Render the folder README on the share page [default: true]
+    #[clap(long)]
+    pub show_readme: Option<bool>,
+
+    /// Let visitors browse the shared folder [default: true]
+    #[clap(long)]
+    pub share_view: Option<bool>,
+
+    /// Points visitors pay to download [default: 0]
+    #[clap(long)]
+    pub price: Option<u32>,
+
+    /// Who can open the link: users (logged-in), public (anyone, including anonymous) or group (your group)
+    #[clap(long, value_parser = ["users", "public", "group"])]
+    pub permission: Option<String>,
+
+    /// Explicit permission setting as JSON, overrides --permission
+    #[clap(long, conflicts_with = "permission")]
+    pub permissions_json: Option<String>,
+}
+
+impl ShareOptions {
+    /// Builds the `permissions` object of a share request
+    pub fn permissions(&self) -> Result<Value> {
+        if let Some(raw) = &self.permissions_json {
+            return serde_json::from_str(raw)
+                .map_err(|e| Error::InvalidResponse(format!("Invalid --permissions-json: {}", e)));
+        }
+
+        let (same_group, everyone, anonymous) = match self.permission.as_deref() {
+            Some("public") => ("read", "read", "read"),
+            Some("group") => ("read", "none", "none"),
+            _ => ("read", "read", "none"),
+        };
+        Ok(json!({
+            "user_explicit": {},
+            "group_explicit": {},
+            "same_group": same_group,
+            "other": "none",
+            "anonymous": anonymous,
+            "everyone": everyone,
+        }))
+    }
+}
+
+/// Share URL with the password appended, the form the web UI hands out
+pub fn url_with_password(url: &str, password: Option<&str>) -> String {
+    match password.filter(|p| !p.is_empty()) {
+        Some(password) if !url.ends_with(&format!("/{}", password)) => {
+            format!("{}/{}", url.trim_end_matches('/'), password)
+        }
+        _ => url.to_string(),
+    }
+}
+
+/// Filters shared by the share subcommands that work on many links
+#[derive(clap::Args, Clone, Debug, Default)]
+pub struct ShareFilter {
+    /// Only links that have expired
+    #[clap(long)]
+    pub expired: bool,
+
+    /// Only password-protected links
+    #[clap(long)]
+    pub password_protected: bool,
+
+    /// Only links sharing files at or below this path
+    #[clap(long)]
+    pub path: Option<String>,
+
+    /// Only links created at or after this time (YYYY-MM-DD, RFC 3339, or an age like 7d)
+    #[clap(long)]
+    pub created_since: Option<String>,
+
+    /// Only links created before this time (YYYY-MM-DD, RFC 3339, or an age like 30d)
+    #[clap(long)]
+    pub created_before: Option<String>,
+}
+
+impl ShareFilter {
+    pub fn is_empty(&self) -> bool {
+        !self.expired
+            && !self.password_protected
+            && self.path.is_none()
+            && self.created_since.is_none()
+            && self.created_before.is_none()
+    }
+}
+
+/// Share link as returned by the V4 `/share` listing
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ShareItem {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub expires: Option<String>,
+    #[serde(default)]
+    pub expired: bool,
+    #[serde(default)]
+    pub is_private: bool,
+    #[serde(default)]
+    pub password: Option<String>,
+    #[serde(default)]
+    pub source_uri: Option<String>,
+    #[serde(default)]
+    pub permission_setting: Option<Value>,
+    #[serde(default)]
+    pub share_view: bool,
+    #[serde(default)]
+    pub show_readme: bool,
+    #[serde(default)]
+    pub price: i64,
+    #[serde(default)]
+    pub visited: i64,
+    #[serde(default)]
+    pub downloaded: i64,
+    /// Downloads left before the link expires, when limited
+    #[serde(default)]
+    pub remain_downloads: Option<i64>,
+}
+
+impl ShareItem {
+    pub fn is_protected(&self) -> bool {
+        self.is_private || self.password.as_deref().is_some_and(|p| !p.is_empty())
+    }
+
+    /// Shared path relative to the owner's root
+    pub fn source_path(&self) -> Option<String> {
+        let uri = self.source_uri.as_deref()?;
+        Some(uri_to_path(uri).map_or_else(|_| uri.to_string(), str::to_string))
+    }
+
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        time::parse_remote_time(self.expires.as_deref()?)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expired || self.expires_at().is_some_and(|at| at <= Utc::now())
+    }
+}
+
+/// Compiled form of `ShareFilter`
+struct Matcher {
+    filter: ShareFilter,
+    source: Option<String>,
+    since: Option<DateTime<Utc>>,
+    before: Option<DateTime<Utc>>,
+}
+
+impl Matcher {
+    fn new(filter: &ShareFilter) -> Result<Self> {
+        let parse_time = |spec: &str| time::parse_time_spec(spec).map_err(Error::InvalidResponse);
+
+        Ok(Matcher {
+            filter: filter.clone(),
+            source: filter
+                .path
+                .as_ref()
+                .map(|p| format!("/{}", p.trim_matches('/'))),
+            since: filter
+                .created_since
+                .as_deref()
+                .map(parse_time)
+                .transpose()?,
+            before: filter
+                .created_before
+                .as_deref()
+                .map(parse_time)
+                .transpose()?,
+        })
+    }
+
+    fn matches(&self, share: &ShareItem) -> bool {
+        if self.filter.expired && !share.is_expired() {
+            return false;
+        }
+        if self.filter.password_protected && !share.is_protected() {
+            return false;
+        }
+
+        if let Some(source) = &self.source {
+            let Some(path) = share.source_path() else {
+                return false;
+            };
+            if !(source == "/" || path == *source || path.starts_with(&format!("{}/", source))) {
+                return false;
+            }
+        }
+
+        if self.since.is_some() || self.before.is_some() {
+            let Some(created) = time::parse_remote_time(&share.created_at) else {
+                return false;
+            };
+            if self.since.is_some_and(|since| created < since)
+                || self.before.is_some_and(|before| created >= before)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Lists all my share links, following pagination, and keeps those matching the filter
+pub async fn fetch_shares(
+    api: &CloudreveAPI,
+    filter: &ShareFilter,
+    order_by: Option<&str>,
+    page_size: u32,
+) -> Result<Vec<ShareItem>> {
+    let matcher = Matcher::new(filter)?;
+
+    let shares = match api.inner() {
+        UnifiedClient::V4(client) => list_v4(client, order_by, page_size).await?,
+        UnifiedClient::V3(_) => {
+            if filter.path.is_some() {
+                warn!("V3 servers do not report share sources, --path matches nothing");
+            }
+            v3::list_shares(api).await?
+        }
+    };
+
+    Ok(shares.into_iter().filter(|s| matcher.matches(s)).collect())
+}
+
+async fn list_v4(
+    client: &CloudreveClient,
+    order_by: Option<&str>,
+    page_size: u32,
+) -> Result<Vec<ShareItem>> {
+    let mut shares = Vec::new();
+    let mut next_token: Option<String> = None;
+
+    loop {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        query.append_pair("page_size", &page_size.to_string());
+        if let Some(order_by) = order_by {
+            query.append_pair("order_by", order_by);
+        }
+        if let Some(token) = &next_token {
+            query.append_pair("next_page_token", token);
+        }
+
+        let response: Value =
+            envelope::data(client.get(&format!("/share?{}", query.finish())).await?)?;
+        let page: Vec<ShareItem> = serde_json::from_value(
+            response
+                .get("shares")
+                .cloned()
+                .unwrap_or_else(|| Value::Array(Vec::new())),
+        )?;
+        shares.extend(page);
+
+        next_token = response
+            .pointer("/pagination/next_token")
+            .and_then(|v| v.as_str())
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_string());
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(shares)
+}
+
+/// Deletes one share link by ID (V4) or key (V3)
+pub async fn delete_share(api: &CloudreveAPI, id: &str) -> Result<()> {
+    match api.inner() {
+        UnifiedClient::V4(client) => client.delete_share_link(id).await,
+        UnifiedClient::V3(_) => v3::delete_share(api, id).await,
+    }
+}
+
+/// Resolves share IDs and/or a filter to the links to act on
+pub async fn select_shares(
+    api: &CloudreveAPI,
+    ids: &[String],
+    filter: &ShareFilter,
+) -> Result<Vec<ShareItem>> {
+    if ids.is_empty() && filter.is_empty() {
+        return Err(Error::InvalidResponse(
+            "Specify share IDs with --id or at least one filter".to_string(),
+        ));
+    }
+
+    let mut shares = fetch_shares(api, filter, None, 100).await?;
+    if !ids.is_empty() {
+        let missing: Vec<&String> = ids
+            .iter()
+            .filter(|id| !shares.iter().any(|s| &s.id == *id))
+            .collect();
+        if !missing.is_empty() && filter.is_empty() {
+            return Err(Error::InvalidResponse(format!(
+                "Share link(s) not found: {}",
+                missing
+                    .iter()
+                    .map(|id| id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+        shares.retain(|s| ids.contains(&s.id));
+    }
+    Ok(shares)
+}
 
 #[derive(clap::Subcommand)]
 pub enum ShareCommands {
@@ -16,13 +350,27 @@ pub enum ShareCommands {
         /// Order by field
         #[clap(long)]
         order_by: Option<String>,
+
+        /// Only these share IDs (repeatable)
+        #[clap(long = "id")]
+        ids: Vec<String>,
+
+        #[clap(flatten)]
+        filter: ShareFilter,
+
+        #[clap(flatten)]
+        qr: QrOptions,
     },
 
-    /// Create a new share link
+    /// Create a new share link, or one per path with --from-file
     Create {
         /// File URI to share
-        #[clap(long)]
-        uri: String,
+        #[clap(long, required_unless_present = "from_file")]
+        uri: Option<String>,
+
+        /// Create one link per path or glob listed in this file (one per line)
+        #[clap(long, conflicts_with_all = ["uri", "name"])]
+        from_file: Option<String>,
 
         /// Share link name
         #[clap(long)]
@@ -33,61 +381,293 @@ pub enum ShareCommands {
         expire: Option<u32>,
 
         /// Password for the share link
-        #[clap(long)]
+        #[clap(long, conflicts_with_all = ["password_stdin", "password_file"])]
         password: Option<String>,
+
+        /// Read the share password from the first line of stdin
+        #[clap(long, conflicts_with = "password_file")]
+        password_stdin: bool,
+
+        /// Read the share password from a file
+        #[clap(long)]
+        password_file: Option<String>,
+
+        /// Generate a random password for each link
+        #[clap(long, conflicts_with_all = ["password", "password_stdin", "password_file"])]
+        generate_password: bool,
+
+        /// Write the manifest (path, URL, password, expiry) to this file instead of stdout
+        #[clap(long, requires = "from_file")]
+        manifest: Option<String>,
+
+        /// Manifest format [default: json for .json files, else csv]
+        #[clap(long, value_parser = ["csv", "json"], requires = "from_file")]
+        manifest_format: Option<String>,
+
+        /// Number of links created concurrently with --from-file
+        #[clap(short, long, default_value = "5")]
+        concurrency: usize,
+
+        #[clap(flatten)]
+        options: ShareOptions,
+
+        #[clap(flatten)]
+        qr: QrOptions,
     },
 
-    /// Update a share link
+    /// Update share links by ID or filter
     Update {
-        /// Share ID
-        #[clap(long)]
-        id: String,
+        /// Share ID (repeatable)
+        #[clap(long = "id")]
+        ids: Vec<String>,
+
+        #[clap(flatten)]
+        filter: ShareFilter,
 
         /// New name
         #[clap(long)]
         name: Option<String>,
 
         /// New expiration time in seconds
-        #[clap(long)]
+        #[clap(long, conflicts_with = "extend")]
         expire: Option<u32>,
 
+        /// Push the expiry back by this much (e.g., 7d, 12h)
+        #[clap(long)]
+        extend: Option<String>,
+
         /// New password
         #[clap(long)]
         password: Option<String>,
     },
 
-    /// Delete a share link
+    /// Delete share links by ID or filter
     Delete {
-        /// Share ID
+        /// Share ID (repeatable)
+        #[clap(long = "id")]
+        ids: Vec<String>,
+
+        #[clap(flatten)]
+        filter: ShareFilter,
+
+        /// Show what would be deleted without deleting
+        #[clap(long)]
+        dry_run: bool,
+
+        /// Skip confirmation prompt
+        #[clap(long)]
+        force: bool,
+    },
+
+    /// Delete expired share links (or those matching a filter)
+    Prune {
+        #[clap(flatten)]
+        filter: ShareFilter,
+
+        /// Show what would be deleted without deleting
+        #[clap(long)]
+        dry_run: bool,
+
+        /// Skip confirmation prompt
         #[clap(long)]
-        id: String,
+        force: bool,
+    },
+
+    /// Show a share link's details and contents, no login needed (V4 instances)
+    Get {
+        /// Share URL (https://host/s/<id>[/<password>])
+        url: String,
+
+        /// Share password
+        #[clap(long)]
+        password: Option<String>,
+
+        /// Print share details and contents as JSON
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Save a share link's files into my storage (V4, same instance)
+    Save {
+        /// Share URL (https://host/s/<id>[/<password>])
+        url: String,
+
+        /// Destination folder in my storage
+        #[clap(long)]
+        to: String,
+
+        /// Share password
+        #[clap(long)]
+        password: Option<String>,
+
+        /// Sub-path inside a folder share to save instead of the whole share
+        #[clap(long)]
+        path: Option<String>,
+    },
+
+    /// Download a shared file or folder, no login needed (V4 instances)
+    Download {
+        /// Share URL (https://host/s/<id>[/<password>])
+        url: String,
+
+        /// Share password
+        #[clap(long)]
+        password: Option<String>,
+
+        /// Local output directory
+        #[clap(short, long, default_value = ".")]
+        path: String,
+
+        /// Number of concurrent downloads
+        #[clap(short, long, default_value = "5")]
+        concurrency: usize,
     },
 }
 
+impl ShareCommands {
+    /// Commands that open other people's links and run without a login
+    pub fn is_public(&self) -> bool {
+        matches!(
+            self,
+            ShareCommands::Get { .. } | ShareCommands::Download { .. }
+        )
+    }
+}
+
+/// Handles the public share commands with the plain HTTP client
+pub async fn handle_public_command(http: &reqwest::Client, command: &ShareCommands) -> Result<()> {
+    match command {
+        ShareCommands::Get {
+            url,
+            password,
+            json,
+        } => public::handle_get(http, url.clone(), password.clone(), *json).await,
+        ShareCommands::Download {
+            url,
+            password,
+            path,
+            concurrency,
+        } => {
+            public::handle_download(
+                http,
+                url.clone(),
+                password.clone(),
+                path.clone(),
+                *concurrency,
+            )
+            .await
+        }
+        _ => unreachable!("only public share commands run without a login"),
+    }
+}
+
 pub async fn handle_share_command(api: &CloudreveAPI, command: ShareCommands) -> Result<()> {
-    // For now, use V4 client through inner()
-    match api.inner() {
-        UnifiedClient::V4(client) => match command {
-            ShareCommands::List {
-                page_size,
-                order_by,
-            } => list::handle_list(client, page_size, order_by).await,
-            ShareCommands::Create {
-                uri,
-                name,
-                expire,
-                password,
-            } => create::handle_create(client, uri, name, expire, password).await,
-            ShareCommands::Update {
-                id,
-                name,
-                expire,
-                password,
-            } => update::handle_update(client, id, name, expire, password).await,
-            ShareCommands::Delete { id } => delete::handle_delete(client, id).await,
+    match command {
+        ShareCommands::List {
+            page_size,
+            order_by,
+            ids,
+            filter,
+            qr,
+        } => list::handle_list(api, page_size, order_by, ids, filter, qr).await,
+        ShareCommands::Create {
+            uri,
+            from_file,
+            name,
+            expire,
+            password,
+            password_stdin,
+            password_file,
+            generate_password,
+            manifest,
+            manifest_format,
+            concurrency,
+            options,
+            qr,
+        } => {
+            let password = credentials::resolve_secret(SecretSource {
+                value: password,
+                from_stdin: password_stdin,
+                file: password_file,
+                env_var: None,
+            })?;
+
+            if let Some(from_file) = from_file {
+                if qr.is_enabled() {
+                    warn!("--qr and --qr-file are ignored with --from-file");
+                }
+                let bulk = bulk::BulkCreate {
+                    from_file,
+                    manifest,
+                    manifest_format,
+                    concurrency,
+                };
+                return bulk::handle_bulk_create(
+                    api,
+                    bulk,
+                    expire,
+                    password,
+                    generate_password,
+                    options,
+                )
+                .await;
+            }
+
+            let password = if generate_password {
+                let generated = bulk::generate_password();
+                info!("Generated password: {}", generated);
+                Some(generated)
+            } else {
+                password
+            };
+            let uri = uri.unwrap_or_default();
+            let url = match api.inner() {
+                UnifiedClient::V4(client) => {
+                    create::handle_create(client, uri, name, expire, password.clone(), options)
+                        .await?
+                }
+                UnifiedClient::V3(_) => {
+                    file_share::handle_share(api, uri, name, expire, password.clone(), options)
+                        .await?
+                }
+            };
+            qr.show(&url_with_password(&url, password.as_deref()))
+        }
+        ShareCommands::Update {
+            ids,
+            filter,
+            name,
+            expire,
+            extend,
+            password,
+        } => match api.inner() {
+            UnifiedClient::V4(_) => {
+                update::handle_update(api, ids, filter, name, expire, extend, password).await
+            }
+            UnifiedClient::V3(_) => {
+                let unsupported = name.is_some() || expire.is_some() || extend.is_some();
+                v3::handle_update(api, ids, filter, unsupported, password).await
+            }
         },
-        UnifiedClient::V3(_) => Err(cloudreve_api::Error::InvalidResponse(
-            "Share commands not yet supported for V3 API".to_string(),
-        )),
+        ShareCommands::Delete {
+            ids,
+            filter,
+            dry_run,
+            force,
+        } => delete::handle_delete(api, ids, filter, dry_run, force).await,
+        ShareCommands::Prune {
+            filter,
+            dry_run,
+            force,
+        } => prune::handle_prune(api, filter, dry_run, force).await,
+        ShareCommands::Save {
+            url,
+            to,
+            password,
+            path,
+        } => save::handle_save(api, url, to, password, path).await,
+        ShareCommands::Get { .. } | ShareCommands::Download { .. } => {
+            unreachable!("public share commands are handled before login")
+        }
     }
 }
@@ -0,0 +1,19 @@
+use super::delete::delete_shares;
+use super::{ShareFilter, fetch_shares};
+use cloudreve_api::{CloudreveAPI, Error, Result};
+
+pub async fn handle_prune(
+    api: &CloudreveAPI,
+    filter: ShareFilter,
+    dry_run: bool,
+    force: bool,
+) -> Result<()> {
+    if filter.is_empty() {
+        return Err(Error::InvalidResponse(
+            "Specify --expired or another filter to choose the links to prune".to_string(),
+        ));
+    }
+
+    let shares = fetch_shares(api, &filter, None, 100).await?;
+    delete_shares(api, shares, dry_run, !force).await
+}
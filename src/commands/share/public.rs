@@ -0,0 +1,406 @@
+//! Anonymous access to share links
+//!
+//! Share links may point to any Cloudreve instance, so these requests use the
+//! plain HTTP client against the link's origin instead of the logged-in API
+//! client. Only V4 instances are supported.
+
+use crate::commands::file::download;
+use crate::utils::concurrency::execute_with_concurrency;
+use crate::utils::format_bytes;
+use crate::utils::remote::RemoteFile;
+use cloudreve_api::{Error, Result};
+use log::{info, warn};
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use url::Url;
+
+/// A parsed share link
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShareLink {
+    /// Scheme, host and port of the instance
+    pub origin: String,
+    pub id: String,
+    pub password: Option<String>,
+}
+
+impl ShareLink {
+    /// Parses `https://host/s/<id>[/<password>]`, an explicit password wins
+    pub fn parse(link: &str, password: Option<String>) -> Result<Self> {
+        let url = Url::parse(link)
+            .map_err(|e| Error::InvalidResponse(format!("Invalid share URL {}: {}", link, e)))?;
+
+        let segments: Vec<&str> = url
+            .path_segments()
+            .map(|s| s.filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+        let (id, url_password) = match segments.as_slice() {
+            ["s", id] => (*id, None),
+            ["s", id, password] => (*id, Some(password.to_string())),
+            _ => {
+                return Err(Error::InvalidResponse(format!(
+                    "Not a share URL (expected https://host/s/<id>): {}",
+                    link
+                )));
+            }
+        };
+
+        Ok(ShareLink {
+            origin: url.origin().ascii_serialization(),
+            id: id.to_string(),
+            password: password.or(url_password),
+        })
+    }
+
+    /// URI of a path inside the share, `/` for the shared item itself
+    pub fn uri_for(&self, path: &str) -> Result<Url> {
+        Ok(path
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .fold(self.root_uri()?, |uri, name| child_uri(&uri, name)))
+    }
+
+    /// `cloudreve://<id>[:<password>]@share` URI of the shared item
+    fn root_uri(&self) -> Result<Url> {
+        let mut uri = Url::parse(&format!("cloudreve://{}@share", self.id)).map_err(|e| {
+            Error::InvalidResponse(format!("Invalid share ID {:?}: {}", self.id, e))
+        })?;
+        if let Some(password) = &self.password {
+            let _ = uri.set_password(Some(password));
+        }
+        Ok(uri)
+    }
+}
+
+/// Unauthenticated V4 client for one instance
+struct PublicClient {
+    http: Client,
+    link: ShareLink,
+}
+
+impl PublicClient {
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}/api/v4{}", self.link.origin, path)
+    }
+
+    async fn get(&self, path: &str) -> Result<Value> {
+        let response = self.http.get(self.endpoint(path)).send().await?;
+        unwrap_response(response.json::<Value>().await?)
+    }
+
+    async fn post(&self, path: &str, body: &Value) -> Result<Value> {
+        let response = self
+            .http
+            .post(self.endpoint(path))
+            .json(body)
+            .send()
+            .await?;
+        unwrap_response(response.json::<Value>().await?)
+    }
+
+    /// Share details, failing when the password is missing or wrong
+    async fn share_info(&self) -> Result<Value> {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        if let Some(password) = &self.link.password {
+            query.append_pair("password", password);
+        }
+        let info = self
+            .get(&format!("/share/info/{}?{}", self.link.id, query.finish()))
+            .await?;
+
+        if info["unlocked"] == Value::Bool(false) {
+            return Err(Error::InvalidResponse(
+                "Share is password protected, pass the password with --password".to_string(),
+            ));
+        }
+        Ok(info)
+    }
+
+    /// Lists every item of a folder inside the share
+    async fn list_all(&self, uri: &Url) -> Result<Vec<RemoteFile>> {
+        let mut files = Vec::new();
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let mut query = url::form_urlencoded::Serializer::new(String::new());
+            query.append_pair("uri", uri.as_str());
+            query.append_pair("page", "0");
+            query.append_pair("page_size", "100");
+            if let Some(token) = &next_token {
+                query.append_pair("next_page_token", token);
+            }
+
+            let response = self.get(&format!("/file?{}", query.finish())).await?;
+            let page: Vec<RemoteFile> = serde_json::from_value(
+                response
+                    .get("files")
+                    .cloned()
+                    .unwrap_or_else(|| Value::Array(Vec::new())),
+            )?;
+            files.extend(page);
+
+            next_token = response
+                .pointer("/pagination/next_token")
+                .and_then(|v| v.as_str())
+                .filter(|t| !t.is_empty())
+                .map(|t| t.to_string());
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(files)
+    }
+
+    async fn download_url(&self, uri: &Url) -> Result<String> {
+        let response = self
+            .post(
+                "/file/url",
+                &json!({ "uris": [uri.as_str()], "download": true }),
+            )
+            .await?;
+        let url = response
+            .pointer("/urls/0/url")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::InvalidResponse("No download URL returned".to_string()))?;
+
+        // Local storage answers with a path on the instance
+        if url.starts_with('/') {
+            Ok(format!("{}{}", self.link.origin, url))
+        } else {
+            Ok(url.to_string())
+        }
+    }
+}
+
+/// Unwraps the `{code, data, msg}` envelope of V4 responses
+fn unwrap_response(response: Value) -> Result<Value> {
+    let code = response["code"].as_i64().unwrap_or(-1) as i32;
+    if code != 0 {
+        return Err(Error::Api {
+            code,
+            message: response["msg"]
+                .as_str()
+                .unwrap_or("Request failed")
+                .to_string(),
+        });
+    }
+    Ok(response["data"].clone())
+}
+
+fn is_folder_share(info: &Value) -> bool {
+    info["source_type"].as_i64() == Some(1)
+}
+
+/// Appends a child name to a share URI
+fn child_uri(parent: &Url, name: &str) -> Url {
+    let mut uri = parent.clone();
+    if let Ok(mut segments) = uri.path_segments_mut() {
+        segments.pop_if_empty().push(name);
+    }
+    uri
+}
+
+/// Rejects names that would escape the output directory
+fn safe_name(name: &str) -> Option<&str> {
+    let unsafe_name = name.is_empty()
+        || name == "."
+        || name == ".."
+        || name.contains(['/', '\\'])
+        || Path::new(name).is_absolute();
+    (!unsafe_name).then_some(name)
+}
+
+pub async fn handle_get(
+    http: &Client,
+    url: String,
+    password: Option<String>,
+    json: bool,
+) -> Result<()> {
+    let client = PublicClient {
+        http: http.clone(),
+        link: ShareLink::parse(&url, password)?,
+    };
+    let info = client.share_info().await?;
+
+    let files = if is_folder_share(&info) {
+        client.list_all(&client.link.root_uri()?).await?
+    } else {
+        Vec::new()
+    };
+
+    if json {
+        let output = json!({ "share": info, "files": files });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let field = |key: &str| match &info[key] {
+        Value::Null => "-".to_string(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+
+    info!("🔗 Share Information:");
+    info!("  Name: {}", field("name"));
+    info!("  ID: {}", client.link.id);
+    info!(
+        "  Type: {}",
+        if is_folder_share(&info) {
+            "Folder"
+        } else {
+            "File"
+        }
+    );
+    info!(
+        "  Owner: {}",
+        info.pointer("/owner/nickname")
+            .and_then(|v| v.as_str())
+            .unwrap_or("-")
+    );
+    info!("  Created: {}", field("created_at"));
+    info!("  Expires: {}", field("expires"));
+    info!("  Views: {}", field("visited"));
+    info!("  Downloads: {}", field("downloaded"));
+    if info["price"].as_i64().unwrap_or(0) > 0 {
+        info!("  Price: {} points", field("price"));
+    }
+
+    if is_folder_share(&info) {
+        info!("");
+        info!("Contents ({} items):", files.len());
+        for file in &files {
+            if file.is_folder() {
+                info!("  📁 {}/", file.name);
+            } else {
+                info!("  📄 {} ({})", file.name, format_bytes(file.size));
+            }
+        }
+    } else if let Some(size) = info["size"].as_i64() {
+        info!("  Size: {}", format_bytes(size));
+    }
+
+    Ok(())
+}
+
+pub async fn handle_download(
+    http: &Client,
+    url: String,
+    password: Option<String>,
+    output: String,
+    concurrency: usize,
+) -> Result<()> {
+    let client = PublicClient {
+        http: http.clone(),
+        link: ShareLink::parse(&url, password)?,
+    };
+    let info = client.share_info().await?;
+    let name = info["name"]
+        .as_str()
+        .and_then(safe_name)
+        .unwrap_or(client.link.id.as_str())
+        .to_string();
+
+    std::fs::create_dir_all(&output)?;
+    let root = client.link.root_uri()?;
+
+    // (share URI, local path) of every file to fetch
+    let mut files = Vec::new();
+    if is_folder_share(&info) {
+        info!("Listing shared folder {}...", name);
+        let mut frontier = vec![(root, Path::new(&output).join(&name))];
+        while let Some((uri, dir)) = frontier.pop() {
+            std::fs::create_dir_all(&dir)?;
+            for entry in client.list_all(&uri).await? {
+                let Some(entry_name) = safe_name(&entry.name) else {
+                    warn!("Skipping unsafe name: {:?}", entry.name);
+                    continue;
+                };
+                let target = (child_uri(&uri, entry_name), dir.join(entry_name));
+                if entry.is_folder() {
+                    frontier.push(target);
+                } else {
+                    files.push(target);
+                }
+            }
+        }
+    } else {
+        files.push((root, Path::new(&output).join(&name)));
+    }
+
+    info!("Downloading {} file(s) to {}", files.len(), output);
+
+    let tasks: Vec<_> = files
+        .into_iter()
+        .map(|(uri, path): (Url, PathBuf)| {
+            let client = PublicClient {
+                http: http.clone(),
+                link: client.link.clone(),
+            };
+            let label = path.display().to_string();
+            (label.clone(), async move {
+                let download_url = client.download_url(&uri).await?;
+                download::save_url(&client.http, &download_url, &label).await
+            })
+        })
+        .collect();
+
+    let total = tasks.len();
+    let mut success = 0;
+    let mut failed = 0;
+    let mut bytes = 0;
+    for (path, result) in execute_with_concurrency(tasks, concurrency).await {
+        match result {
+            Ok(size) => {
+                success += 1;
+                bytes += size;
+            }
+            Err(e) => {
+                failed += 1;
+                warn!("Failed to download {}: {}", path, e);
+            }
+        }
+    }
+
+    info!(
+        "Download completed: {} succeeded, {} failed, {}",
+        success,
+        failed,
+        format_bytes(bytes as i64)
+    );
+
+    if failed > 0 {
+        return Err(Error::InvalidResponse(format!(
+            "Failed to download {} out of {} files",
+            failed, total
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_share_link() {
+        let link = ShareLink::parse("https://drive.example.com/s/AbC1/pw99", None).unwrap();
+        assert_eq!(link.origin, "https://drive.example.com");
+        assert_eq!(link.id, "AbC1");
+        assert_eq!(link.password.as_deref(), Some("pw99"));
+
+        // 显式密码优先于链接中的密码
+        let link = ShareLink::parse("http://host:5212/s/AbC1/pw99", Some("x".into())).unwrap();
+        assert_eq!(link.origin, "http://host:5212");
+        assert_eq!(link.password.as_deref(), Some("x"));
+
+        assert_eq!(
+            link.uri_for("/docs/a b.txt").unwrap().as_str(),
+            "cloudreve://AbC1:x@share/docs/a%20b.txt"
+        );
+
+        assert!(ShareLink::parse("https://host/home", None).is_err());
+        assert_eq!(safe_name(".."), None);
+        assert_eq!(safe_name("a/b"), None);
+    }
+}
@@ -0,0 +1,78 @@
+use super::public::ShareLink;
+use crate::utils::{envelope, remote};
+use cloudreve_api::{CloudreveAPI, Error, Result, UnifiedClient};
+use log::info;
+use serde_json::{Value, json};
+
+/// Copies the files of a share link into the current user's storage
+pub async fn handle_save(
+    api: &CloudreveAPI,
+    url: String,
+    to: String,
+    password: Option<String>,
+    path: Option<String>,
+) -> Result<()> {
+    let UnifiedClient::V4(client) = api.inner() else {
+        return Err(Error::InvalidResponse(
+            "Saving shares is not yet supported for V3 API".to_string(),
+        ));
+    };
+
+    let link = ShareLink::parse(&url, password)?;
+
+    // The server can only copy shares it hosts itself
+    let own_origin = url::Url::parse(api.base_url())
+        .map(|u| u.origin().ascii_serialization())
+        .unwrap_or_default();
+    if link.origin != own_origin {
+        return Err(Error::InvalidResponse(format!(
+            "Share is hosted on {} but you are logged in to {}; log in there first or use `share download`",
+            link.origin, own_origin
+        )));
+    }
+
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    if let Some(password) = &link.password {
+        query.append_pair("password", password);
+    }
+    let share: Value = envelope::data(
+        client
+            .get(&format!("/share/info/{}?{}", link.id, query.finish()))
+            .await?,
+    )?;
+    if share["unlocked"] == Value::Bool(false) {
+        return Err(Error::InvalidResponse(
+            "Share is password protected, pass the password with --password".to_string(),
+        ));
+    }
+
+    let sub_path = path.unwrap_or_default();
+    if !sub_path.trim_matches('/').is_empty() && share["source_type"].as_i64() != Some(1) {
+        return Err(Error::InvalidResponse(
+            "--path can only be used with folder shares".to_string(),
+        ));
+    }
+
+    let source = link.uri_for(&sub_path)?;
+    let destination = remote::to_uri(&to);
+    info!(
+        "Saving {}{} to {}",
+        share["name"].as_str().unwrap_or(link.id.as_str()),
+        if sub_path.trim_matches('/').is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", sub_path)
+        },
+        to
+    );
+
+    let request = json!({
+        "uris": [source.as_str()],
+        "dst": destination,
+        "copy": true,
+    });
+    envelope::check(client.post("/file/move", &request).await?)?;
+
+    info!("Saved to {}", to);
+    Ok(())
+}
@@ -1,38 +1,119 @@
-use cloudreve_api::api::v4::models::{EditShareLinkRequest, PermissionSetting};
-use cloudreve_api::{CloudreveClient, Result};
-use log::info;
-use serde_json::json;
+use super::{ShareFilter, ShareItem, ShareOptions, select_shares};
+use crate::utils::{envelope, time};
+use chrono::{Duration, Utc};
+use cloudreve_api::{CloudreveAPI, Error, Result, UnifiedClient};
+use log::{error, info, warn};
+use serde_json::{Value, json};
 
 pub async fn handle_update(
-    client: &CloudreveClient,
-    id: String,
-    _name: Option<String>,
+    api: &CloudreveAPI,
+    ids: Vec<String>,
+    filter: ShareFilter,
+    name: Option<String>,
     expire: Option<u32>,
-    _password: Option<String>,
+    extend: Option<String>,
+    password: Option<String>,
 ) -> Result<()> {
-    info!("Updating share link: {}", id);
-
-    // Create a default permission setting
-    let permission = PermissionSetting {
-        user_explicit: json!({}),
-        group_explicit: json!({}),
-        same_group: String::new(),
-        other: String::new(),
-        anonymous: String::new(),
-        everyone: String::new(),
-    };
+    let extend = extend
+        .map(|e| time::parse_duration(&e).map_err(Error::InvalidResponse))
+        .transpose()?;
 
-    let request = EditShareLinkRequest {
-        permissions: permission,
-        uri: String::new(), // Empty URI means not changing
-        share_view: None,
-        show_readme: None,
-        expire,
-        price: None,
+    let UnifiedClient::V4(client) = api.inner() else {
+        return Err(Error::InvalidResponse("V4 client required".to_string()));
     };
 
-    client.edit_share_link(&id, &request).await?;
+    let shares = select_shares(api, &ids, &filter).await?;
+    if shares.is_empty() {
+        info!("No matching share links");
+        return Ok(());
+    }
+
+    let mut updated = 0;
+    let mut failed = 0;
+    for share in &shares {
+        info!("Updating share link: {}", share.id);
+
+        let Some(request) = build_request(share, &name, expire, extend, &password)? else {
+            warn!("  {} never expires, nothing to extend", share.id);
+            continue;
+        };
+
+        match client
+            .post(&format!("/share/{}", share.id), &request)
+            .await
+            .and_then(envelope::check)
+        {
+            Ok(_) => updated += 1,
+            Err(e) => {
+                failed += 1;
+                error!("Failed to update {}: {}", share.id, e);
+            }
+        }
+    }
 
-    info!("Share link updated successfully");
+    info!("Updated {} share link(s), {} failed", updated, failed);
     Ok(())
 }
+
+/// Builds the edit request, keeping every setting that is not changed
+///
+/// The edit endpoint replaces the whole link, so the current permissions,
+/// options, remaining lifetime and remaining downloads are sent back. Returns `None` when
+/// `--extend` is used on a link without expiry.
+fn build_request(
+    share: &ShareItem,
+    name: &Option<String>,
+    expire: Option<u32>,
+    extend: Option<Duration>,
+    password: &Option<String>,
+) -> Result<Option<Value>> {
+    let now = Utc::now();
+    let expires_at = share.expires_at();
+
+    let expire = match (expire, extend) {
+        (Some(expire), _) => Some(expire as i64),
+        (None, Some(extend)) => {
+            let Some(expires_at) = expires_at else {
+                return Ok(None);
+            };
+            // Expired links are extended from now
+            let new_expiry = expires_at.max(now) + extend;
+            info!("  New expiry: {}", new_expiry.to_rfc3339());
+            Some((new_expiry - now).num_seconds())
+        }
+        // Links past their expiry stay expired
+        (None, None) => expires_at.map(|at| (at - now).num_seconds().max(1)),
+    };
+
+    let permissions = match &share.permission_setting {
+        Some(permissions) => permissions.clone(),
+        None => ShareOptions::default().permissions()?,
+    };
+    let password = password
+        .clone()
+        .or_else(|| share.password.clone().filter(|p| !p.is_empty()));
+
+    let mut request = json!({
+        "permissions": permissions,
+        "uri": share.source_uri.clone().unwrap_or_default(),
+        "is_private": password.is_some(),
+        "share_view": share.share_view,
+        "show_readme": share.show_readme,
+        "price": share.price,
+    });
+    if let Some(name) = name {
+        request["name"] = json!(name);
+    }
+    if let Some(expire) = expire {
+        request["expire"] = json!(expire);
+    }
+    if let Some(password) = password {
+        request["password"] = json!(password);
+    }
+    // The limit restarts from the value sent, so pass on what is left
+    if let Some(remaining) = share.remain_downloads {
+        request["downloads"] = json!(remaining);
+    }
+
+    Ok(Some(request))
+}
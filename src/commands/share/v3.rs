@@ -0,0 +1,167 @@
+//! Share management on V3 servers
+//!
+//! V3 links are addressed by key, list without source paths and can only
+//! change their password and preview setting after creation.
+
+use super::{ShareFilter, ShareItem, select_shares};
+use crate::utils::envelope;
+use chrono::{Duration, Utc};
+use cloudreve_api::{CloudreveAPI, Error, Result, UnifiedClient};
+use log::{error, info};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+/// Share link as returned by the V3 `/share` listing
+#[derive(Debug, Deserialize)]
+struct V3Share {
+    key: String,
+    #[serde(default)]
+    password: String,
+    #[serde(default)]
+    create_date: String,
+    #[serde(default)]
+    downloads: i64,
+    /// -1 when unlimited
+    #[serde(default = "unlimited")]
+    remain_downloads: i64,
+    #[serde(default)]
+    views: i64,
+    /// Seconds left, negative once expired, `NEVER_EXPIRES` without an expiry
+    #[serde(default = "unlimited")]
+    expire: i64,
+    #[serde(default)]
+    preview: bool,
+    #[serde(default)]
+    source: Option<V3ShareSource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct V3ShareSource {
+    #[serde(default)]
+    name: String,
+}
+
+/// `expire` of a link without an expiry time
+const NEVER_EXPIRES: i64 = -1;
+
+fn unlimited() -> i64 {
+    -1
+}
+
+impl V3Share {
+    fn into_item(self, base_url: &str) -> ShareItem {
+        let now = Utc::now();
+        ShareItem {
+            url: format!("{}/s/{}", base_url.trim_end_matches('/'), self.key),
+            name: self.source.map(|s| s.name).unwrap_or_default(),
+            created_at: self.create_date,
+            expires: (self.expire != NEVER_EXPIRES)
+                .then(|| (now + Duration::seconds(self.expire)).to_rfc3339()),
+            // The countdown keeps going below zero once the link has expired
+            expired: (self.expire != NEVER_EXPIRES && self.expire <= 0)
+                || self.remain_downloads == 0,
+            is_private: !self.password.is_empty(),
+            password: Some(self.password).filter(|p| !p.is_empty()),
+            share_view: self.preview,
+            visited: self.views,
+            downloaded: self.downloads,
+            id: self.key,
+            ..Default::default()
+        }
+    }
+}
+
+/// Lists every share link of the current user
+pub async fn list_shares(api: &CloudreveAPI) -> Result<Vec<ShareItem>> {
+    let UnifiedClient::V3(client) = api.inner() else {
+        return Err(Error::InvalidResponse("V3 client required".to_string()));
+    };
+    let mut shares = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let response: Value = envelope::data(
+            client
+                .get(&format!(
+                    "/share?page={}&order_by=created_at&order=DESC",
+                    page
+                ))
+                .await?,
+        )?;
+        let items: Vec<V3Share> = serde_json::from_value(
+            response
+                .get("items")
+                .cloned()
+                .unwrap_or_else(|| Value::Array(Vec::new())),
+        )?;
+        let total = response["total"].as_u64().unwrap_or(0) as usize;
+
+        if items.is_empty() {
+            break;
+        }
+        shares.extend(items.into_iter().map(|s| s.into_item(api.base_url())));
+        if shares.len() >= total {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(shares)
+}
+
+pub async fn delete_share(api: &CloudreveAPI, key: &str) -> Result<()> {
+    let UnifiedClient::V3(client) = api.inner() else {
+        return Err(Error::InvalidResponse("V3 client required".to_string()));
+    };
+    envelope::check(client.delete(&format!("/share/{}", key)).await?)
+}
+
+/// Changes the password of V3 share links, the only editable field besides preview
+pub async fn handle_update(
+    api: &CloudreveAPI,
+    ids: Vec<String>,
+    filter: ShareFilter,
+    unsupported: bool,
+    password: Option<String>,
+) -> Result<()> {
+    if unsupported {
+        return Err(Error::InvalidResponse(
+            "V3 share links cannot change name or expiry, only --password".to_string(),
+        ));
+    }
+    let Some(password) = password else {
+        return Err(Error::InvalidResponse(
+            "Nothing to update, pass --password".to_string(),
+        ));
+    };
+
+    let UnifiedClient::V3(client) = api.inner() else {
+        return Err(Error::InvalidResponse("V3 client required".to_string()));
+    };
+    let shares = select_shares(api, &ids, &filter).await?;
+    if shares.is_empty() {
+        info!("No matching share links");
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for share in &shares {
+        info!("Updating share link: {}", share.id);
+        let request = json!({ "prop": "password", "value": password });
+        if let Err(e) = client
+            .patch(&format!("/share/{}", share.id), &request)
+            .await
+            .and_then(envelope::check)
+        {
+            failed += 1;
+            error!("Failed to update {}: {}", share.id, e);
+        }
+    }
+
+    info!(
+        "Updated {} share link(s), {} failed",
+        shares.len() - failed,
+        failed
+    );
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use super::{TrashFilter, fetch_items, trash_retention};
+use crate::utils::{envelope, format_bytes, time};
+use chrono::Utc;
+use cloudreve_api::api::v4::models::DeleteFileRequest;
+use cloudreve_api::{CloudreveClient, Error, Result};
+use log::info;
+use std::io::{self, Write};
+
+pub async fn handle_empty(
+    client: &CloudreveClient,
+    older_than: Option<String>,
+    filter: TrashFilter,
+    dry_run: bool,
+    force: bool,
+) -> Result<()> {
+    let cutoff = older_than
+        .map(|age| time::parse_duration(&age).map_err(Error::InvalidResponse))
+        .transpose()?
+        .map(|age| Utc::now() - age);
+
+    // Deletion times come from the purge schedule and the retention period,
+    // so --older-than cannot be honoured without the latter
+    let retention = match cutoff {
+        Some(_) => Some(trash_retention(client).await?.ok_or_else(|| {
+            Error::InvalidResponse(
+                "--older-than needs the trash retention period, which the server did not report"
+                    .to_string(),
+            )
+        })?),
+        None => None,
+    };
+
+    let mut items = fetch_items(client, &filter, retention).await?;
+    if let Some(cutoff) = cutoff {
+        items.retain(|item| {
+            item.deleted_at
+                .is_some_and(|deleted_at| deleted_at < cutoff)
+        });
+    }
+
+    if items.is_empty() {
+        info!("Nothing to delete");
+        return Ok(());
+    }
+
+    let total_size: i64 = items.iter().map(|i| i.file.size).sum();
+    info!(
+        "Items to delete permanently: {} ({})",
+        items.len(),
+        format_bytes(total_size)
+    );
+    for item in &items {
+        info!(
+            "  - {} (from {})",
+            item.file.name,
+            item.original_path.as_deref().unwrap_or("unknown")
+        );
+    }
+
+    if dry_run {
+        info!("Dry run, nothing deleted");
+        return Ok(());
+    }
+
+    if !force {
+        print!(
+            "Permanently delete {} item(s)? This cannot be undone. [y/N]: ",
+            items.len()
+        );
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            info!("Operation cancelled");
+            return Ok(());
+        }
+    }
+
+    let uris: Vec<&str> = items.iter().map(|i| i.file.path.as_str()).collect();
+    let request = DeleteFileRequest {
+        uris,
+        unlink: None,
+        skip_soft_delete: Some(true),
+    };
+    envelope::check(client.delete_with_body("/file", &request).await?)?;
+
+    info!(
+        "🗑️  Permanently deleted {} item(s), freed {}",
+        items.len(),
+        format_bytes(total_size)
+    );
+    Ok(())
+}
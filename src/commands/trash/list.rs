@@ -0,0 +1,55 @@
+use super::{TrashFilter, fetch_items, trash_retention};
+use crate::utils::format_bytes;
+use cloudreve_api::{CloudreveClient, Result};
+use log::{info, warn};
+
+pub async fn handle_list(client: &CloudreveClient, filter: TrashFilter) -> Result<()> {
+    info!("Listing trash bin...");
+
+    let retention = trash_retention(client).await.unwrap_or_else(|e| {
+        warn!("Could not read the trash retention period: {}", e);
+        None
+    });
+    let items = fetch_items(client, &filter, retention).await?;
+
+    if items.is_empty() {
+        info!("Trash bin is empty (or nothing matches the filter)");
+        return Ok(());
+    }
+
+    let total_size: i64 = items.iter().map(|i| i.file.size).sum();
+    info!(
+        "Found {} item(s), {} total:",
+        items.len(),
+        format_bytes(total_size)
+    );
+
+    for item in &items {
+        let icon = if item.file.is_folder() {
+            "📁"
+        } else {
+            "📄"
+        };
+        info!("");
+        info!("{} {}", icon, item.file.name);
+        info!(
+            "   Original:  {}",
+            item.original_path.as_deref().unwrap_or("(unknown)")
+        );
+        if let Some(deleted_at) = item.deleted_at {
+            info!("   Deleted:   {}", deleted_at.format("%Y-%m-%d %H:%M:%S"));
+        }
+        if let Some(modified_at) = item.modified_at {
+            info!("   Modified:  {}", modified_at.format("%Y-%m-%d %H:%M:%S"));
+        }
+        if !item.file.is_folder() {
+            info!("   Size:      {}", format_bytes(item.file.size));
+        }
+        if let Some(purge) = item.purge_at {
+            info!("   Purged:    {}", purge.format("%Y-%m-%d %H:%M:%S"));
+        }
+        info!("   URI:       {}", item.file.path);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,263 @@
+// Trash bin management commands
+
+pub mod empty;
+pub mod list;
+pub mod restore;
+
+use crate::utils::remote::{self, EXPECTED_COLLECT_KEY, RESTORE_URI_KEY, RemoteFile, TRASH_URI};
+use crate::utils::{envelope, time};
+use chrono::{DateTime, Duration, Utc};
+use cloudreve_api::api::v4::uri::uri_to_path;
+use cloudreve_api::{CloudreveAPI, CloudreveClient, Error, Result, UnifiedClient};
+use glob::Pattern as GlobPattern;
+use serde_json::Value;
+use std::cmp::Reverse;
+
+/// Filters shared by the trash subcommands
+#[derive(clap::Args, Clone, Default)]
+pub struct TrashFilter {
+    /// Only items whose name matches this glob (e.g., "*.pdf")
+    #[clap(long)]
+    pub name: Option<String>,
+
+    /// Only items originally located at or below this path (globs allowed)
+    #[clap(long)]
+    pub original: Option<String>,
+
+    /// Only items last modified at or after this time (YYYY-MM-DD, RFC 3339, or an age like 7d)
+    #[clap(long)]
+    pub modified_since: Option<String>,
+
+    /// Only items last modified before this time (YYYY-MM-DD, RFC 3339, or an age like 30d)
+    #[clap(long)]
+    pub modified_before: Option<String>,
+}
+
+impl TrashFilter {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.original.is_none()
+            && self.modified_since.is_none()
+            && self.modified_before.is_none()
+    }
+}
+
+#[derive(clap::Subcommand)]
+pub enum TrashCommands {
+    /// List items in the trash bin
+    List {
+        #[clap(flatten)]
+        filter: TrashFilter,
+    },
+
+    /// Restore items from the trash bin to their original location
+    Restore {
+        #[clap(flatten)]
+        filter: TrashFilter,
+
+        /// Restore every item in the trash bin
+        #[clap(long, conflicts_with_all = ["name", "original", "modified_since", "modified_before"])]
+        all: bool,
+
+        /// Show what would be restored without restoring
+        #[clap(long)]
+        dry_run: bool,
+
+        /// Skip confirmation prompt
+        #[clap(long)]
+        force: bool,
+    },
+
+    /// Permanently delete items from the trash bin
+    Empty {
+        /// Only items moved to the trash longer ago than this (e.g., 30d, 12h)
+        #[clap(long)]
+        older_than: Option<String>,
+
+        #[clap(flatten)]
+        filter: TrashFilter,
+
+        /// Show what would be deleted without deleting
+        #[clap(long)]
+        dry_run: bool,
+
+        /// Skip confirmation prompt
+        #[clap(long)]
+        force: bool,
+    },
+}
+
+pub async fn handle_trash_command(api: &CloudreveAPI, command: TrashCommands) -> Result<()> {
+    match api.inner() {
+        UnifiedClient::V4(client) => match command {
+            TrashCommands::List { filter } => list::handle_list(client, filter).await,
+            TrashCommands::Restore {
+                filter,
+                all,
+                dry_run,
+                force,
+            } => restore::handle_restore(client, filter, all, dry_run, force).await,
+            TrashCommands::Empty {
+                older_than,
+                filter,
+                dry_run,
+                force,
+            } => empty::handle_empty(client, older_than, filter, dry_run, force).await,
+        },
+        UnifiedClient::V3(_) => Err(Error::InvalidResponse(
+            "Trash commands not yet supported for V3 API".to_string(),
+        )),
+    }
+}
+
+/// A trash bin entry with its original location resolved
+pub struct TrashItem {
+    pub file: RemoteFile,
+    pub original_path: Option<String>,
+    /// Last modification time, usually but not always when it was trashed
+    pub modified_at: Option<DateTime<Utc>>,
+    /// When the item is purged automatically
+    pub purge_at: Option<DateTime<Utc>>,
+    /// When the item was trashed, known only if the retention period is
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl TrashItem {
+    fn from_remote(file: RemoteFile, retention: Option<Duration>) -> Self {
+        let original_path = file
+            .meta(RESTORE_URI_KEY)
+            .map(|uri| uri_to_path(uri).map_or_else(|_| uri.to_string(), str::to_string));
+        let modified_at = time::parse_remote_time(&file.updated_at);
+        let purge_at = file
+            .meta(EXPECTED_COLLECT_KEY)
+            .and_then(|ts| ts.parse::<i64>().ok())
+            .and_then(|ts| DateTime::from_timestamp(ts, 0));
+        // The metadata only holds the purge schedule, which is set to the
+        // deletion time plus the retention period
+        let deleted_at = purge_at
+            .zip(retention)
+            .map(|(purge, retention)| purge - retention);
+        TrashItem {
+            file,
+            original_path,
+            modified_at,
+            purge_at,
+            deleted_at,
+        }
+    }
+}
+
+/// Trash retention period of the current user's group
+///
+/// `None` when the server does not report one, in which case deletion times
+/// cannot be derived.
+pub async fn trash_retention(client: &CloudreveClient) -> Result<Option<Duration>> {
+    let me: Value = envelope::data(client.get("/user/me").await?)?;
+    Ok(me["group"]["trash_retention"]
+        .as_i64()
+        .filter(|seconds| *seconds > 0)
+        .map(Duration::seconds))
+}
+
+/// Compiled form of `TrashFilter`
+struct Matcher {
+    name: Option<GlobPattern>,
+    original: Option<String>,
+    original_glob: Option<GlobPattern>,
+    since: Option<DateTime<Utc>>,
+    before: Option<DateTime<Utc>>,
+}
+
+impl Matcher {
+    fn new(filter: &TrashFilter) -> Result<Self> {
+        let compile = |pattern: &str| {
+            GlobPattern::new(pattern)
+                .map_err(|e| Error::InvalidResponse(format!("Invalid pattern {}: {}", pattern, e)))
+        };
+        let parse_time = |spec: &str| time::parse_time_spec(spec).map_err(Error::InvalidResponse);
+
+        let original = filter
+            .original
+            .as_ref()
+            .map(|p| format!("/{}", p.trim_matches('/')));
+        let original_glob = match &original {
+            Some(p) if p.contains(['*', '?', '[']) => Some(compile(p)?),
+            _ => None,
+        };
+
+        Ok(Matcher {
+            name: filter.name.as_deref().map(compile).transpose()?,
+            original,
+            original_glob,
+            since: filter
+                .modified_since
+                .as_deref()
+                .map(parse_time)
+                .transpose()?,
+            before: filter
+                .modified_before
+                .as_deref()
+                .map(parse_time)
+                .transpose()?,
+        })
+    }
+
+    fn matches(&self, item: &TrashItem) -> bool {
+        if let Some(pattern) = &self.name
+            && !pattern.matches(&item.file.name)
+        {
+            return false;
+        }
+
+        if let Some(original) = &self.original {
+            let Some(path) = &item.original_path else {
+                return false;
+            };
+            let matched = match &self.original_glob {
+                Some(pattern) => pattern.matches(path),
+                None => {
+                    original == "/"
+                        || path == original
+                        || path.starts_with(&format!("{}/", original))
+                }
+            };
+            if !matched {
+                return false;
+            }
+        }
+
+        if self.since.is_some() || self.before.is_some() {
+            let Some(modified_at) = item.modified_at else {
+                return false;
+            };
+            if self.since.is_some_and(|since| modified_at < since)
+                || self.before.is_some_and(|before| modified_at >= before)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Lists the trash bin and keeps the items matching the filter, newest first
+///
+/// Deletion times are filled in when `retention` is given.
+pub async fn fetch_items(
+    client: &CloudreveClient,
+    filter: &TrashFilter,
+    retention: Option<Duration>,
+) -> Result<Vec<TrashItem>> {
+    let matcher = Matcher::new(filter)?;
+
+    let mut items: Vec<TrashItem> = remote::list_all(client, TRASH_URI, 100)
+        .await?
+        .into_iter()
+        .map(|file| TrashItem::from_remote(file, retention))
+        .filter(|item| matcher.matches(item))
+        .collect();
+
+    items.sort_by_key(|item| Reverse(item.modified_at));
+    Ok(items)
+}
@@ -0,0 +1,62 @@
+use super::{TrashFilter, fetch_items};
+use cloudreve_api::api::v4::models::RestoreFileRequest;
+use cloudreve_api::{CloudreveClient, Error, Result};
+use log::info;
+use std::io::{self, Write};
+
+pub async fn handle_restore(
+    client: &CloudreveClient,
+    filter: TrashFilter,
+    all: bool,
+    dry_run: bool,
+    force: bool,
+) -> Result<()> {
+    if filter.is_empty() && !all {
+        return Err(Error::InvalidResponse(
+            "Specify a filter (--name, --original, --modified-since, --modified-before) or --all"
+                .to_string(),
+        ));
+    }
+
+    let items = fetch_items(client, &filter, None).await?;
+
+    if items.is_empty() {
+        info!("No items in the trash bin match the filter");
+        return Ok(());
+    }
+
+    info!("Items to restore: {}", items.len());
+    for item in &items {
+        info!(
+            "  - {} -> {}",
+            item.file.name,
+            item.original_path.as_deref().unwrap_or("(unknown)")
+        );
+    }
+
+    if dry_run {
+        info!("Dry run, nothing restored");
+        return Ok(());
+    }
+
+    if !force {
+        print!("Restore {} item(s)? [y/N]: ", items.len());
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            info!("Operation cancelled");
+            return Ok(());
+        }
+    }
+
+    let uris: Vec<&str> = items.iter().map(|i| i.file.path.as_str()).collect();
+    client
+        .restore_from_trash(&RestoreFileRequest { uris })
+        .await?;
+
+    info!("✅ Restored {} item(s)", items.len());
+    Ok(())
+}
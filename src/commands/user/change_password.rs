@@ -1,6 +1,43 @@
+use crate::utils::credentials::{self, SecretSource};
 use cloudreve_api::{CloudreveClient, Result};
 use log::info;
 
+/// Resolves current and new passwords from flags, files, stdin or a TTY prompt
+pub fn resolve_passwords(old: SecretSource<'_>, new: SecretSource<'_>) -> Result<(String, String)> {
+    // Both passwords share stdin, one per line, in order
+    let old_password = credentials::require_secret(old, "Enter current password: ")?;
+
+    let new_password = match credentials::resolve_secret(new)? {
+        Some(pwd) => pwd,
+        None => {
+            let first = credentials::prompt_secret("Enter new password: ")?;
+            let second = credentials::prompt_secret("Confirm new password: ")?;
+            match (first, second) {
+                (Some(first), Some(second)) if first == second => first,
+                (Some(_), Some(_)) => {
+                    return Err(cloudreve_api::Error::InvalidResponse(
+                        "New passwords do not match".to_string(),
+                    ));
+                }
+                _ => {
+                    return Err(cloudreve_api::Error::InvalidResponse(
+                        "No new password provided. Use --new-password-file or --password-stdin"
+                            .to_string(),
+                    ));
+                }
+            }
+        }
+    };
+
+    if new_password.is_empty() {
+        return Err(cloudreve_api::Error::InvalidResponse(
+            "New password must not be empty".to_string(),
+        ));
+    }
+
+    Ok((old_password, new_password))
+}
+
 pub async fn handle_change_password(
     client: &CloudreveClient,
     old_password: String,
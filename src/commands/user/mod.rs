@@ -1,10 +1,15 @@
 pub mod change_password;
 pub mod info;
+pub mod passkeys;
 pub mod policies;
 pub mod quota;
+pub mod sessions;
+pub mod two_fa;
 pub mod update_profile;
+pub mod v3;
 
 use crate::context::TokenManager;
+use crate::utils::credentials::{self, SecretSource};
 use cloudreve_api::{CloudreveAPI, Result, UnifiedClient};
 
 #[derive(clap::Subcommand)]
@@ -31,13 +36,44 @@ pub enum UserCommands {
 
     /// Change password
     ChangePassword {
-        /// Current password
+        /// Current password (falls back to CLOUDREVE_PASSWORD)
+        #[clap(long, conflicts_with = "old_password_file")]
+        old_password: Option<String>,
+
+        /// Read the current password from a file
         #[clap(long)]
-        old_password: String,
+        old_password_file: Option<String>,
 
         /// New password
+        #[clap(long, conflicts_with = "new_password_file")]
+        new_password: Option<String>,
+
+        /// Read the new password from a file
         #[clap(long)]
-        new_password: String,
+        new_password_file: Option<String>,
+
+        /// Read passwords from stdin: current password on the first line, new on the second
+        #[clap(long, conflicts_with_all = ["old_password", "old_password_file", "new_password", "new_password_file"])]
+        password_stdin: bool,
+    },
+
+    /// Two-factor authentication management
+    #[clap(name = "2fa")]
+    TwoFa {
+        #[clap(subcommand)]
+        command: two_fa::TwoFaCommands,
+    },
+
+    /// Passkey management
+    Passkeys {
+        #[clap(subcommand)]
+        command: passkeys::PasskeysCommands,
+    },
+
+    /// Show login activity (IP, browser, result and time)
+    Sessions {
+        #[clap(flatten)]
+        options: sessions::SessionsOptions,
     },
 }
 
@@ -49,40 +85,66 @@ pub async fn handle_user_command(
     match command {
         UserCommands::Info => info::handle_info(api, token_manager).await,
         UserCommands::Quota => quota::handle_quota(api).await,
-        UserCommands::Policies => {
-            // Policies is not supported in V3
-            match api.inner() {
-                UnifiedClient::V4(client) => policies::handle_policies(client).await,
-                UnifiedClient::V3(_) => Err(cloudreve_api::Error::InvalidResponse(
-                    "Storage policies list not available in V3 API".to_string(),
-                )),
+        UserCommands::Policies => match api.inner() {
+            UnifiedClient::V4(client) => policies::handle_policies(client).await,
+            UnifiedClient::V3(_) => v3::handle_policies(api).await,
+        },
+        UserCommands::UpdateProfile { nickname, avatar } => match api.inner() {
+            UnifiedClient::V4(client) => {
+                update_profile::handle_update_profile(client, nickname, avatar).await
             }
-        }
-        UserCommands::UpdateProfile { nickname, avatar } => {
-            // Update profile is not supported in V3
-            match api.inner() {
-                UnifiedClient::V4(client) => {
-                    update_profile::handle_update_profile(client, nickname, avatar).await
-                }
-                UnifiedClient::V3(_) => Err(cloudreve_api::Error::InvalidResponse(
-                    "Update profile not available in V3 API".to_string(),
-                )),
-            }
-        }
+            UnifiedClient::V3(_) => v3::handle_update_profile(api, nickname, avatar).await,
+        },
         UserCommands::ChangePassword {
             old_password,
+            old_password_file,
             new_password,
+            new_password_file,
+            password_stdin,
         } => {
-            // Change password is not supported in V3
+            let (old_password, new_password) = change_password::resolve_passwords(
+                SecretSource {
+                    value: old_password,
+                    from_stdin: password_stdin,
+                    file: old_password_file,
+                    env_var: Some(credentials::PASSWORD_ENV),
+                },
+                SecretSource {
+                    value: new_password,
+                    from_stdin: password_stdin,
+                    file: new_password_file,
+                    env_var: None,
+                },
+            )?;
             match api.inner() {
                 UnifiedClient::V4(client) => {
                     change_password::handle_change_password(client, old_password, new_password)
                         .await
                 }
-                UnifiedClient::V3(_) => Err(cloudreve_api::Error::InvalidResponse(
-                    "Change password not available in V3 API".to_string(),
-                )),
+                UnifiedClient::V3(_) => {
+                    v3::handle_change_password(api, old_password, new_password).await
+                }
             }
         }
+        UserCommands::TwoFa { command } => match api.inner() {
+            UnifiedClient::V4(client) => {
+                two_fa::handle_two_fa(api, client, token_manager, command).await
+            }
+            UnifiedClient::V3(_) => Err(cloudreve_api::Error::InvalidResponse(
+                "2FA management not available in V3 API".to_string(),
+            )),
+        },
+        UserCommands::Passkeys { command } => match api.inner() {
+            UnifiedClient::V4(client) => passkeys::handle_passkeys(client, command).await,
+            UnifiedClient::V3(_) => Err(cloudreve_api::Error::InvalidResponse(
+                "Passkey management not available in V3 API".to_string(),
+            )),
+        },
+        UserCommands::Sessions { options } => match api.inner() {
+            UnifiedClient::V4(client) => sessions::handle_sessions(client, options).await,
+            UnifiedClient::V3(_) => Err(cloudreve_api::Error::InvalidResponse(
+                "Login activity not available in V3 API".to_string(),
+            )),
+        },
     }
 }
@@ -0,0 +1,122 @@
+use crate::utils::envelope;
+use cloudreve_api::{CloudreveClient, Error, Result};
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{self, Write};
+
+#[derive(clap::Subcommand)]
+pub enum PasskeysCommands {
+    /// List registered passkeys
+    List {
+        /// Print passkeys as JSON
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Delete one or more passkeys
+    Delete {
+        /// Passkey ID(s)
+        #[clap(required = true)]
+        ids: Vec<String>,
+
+        /// Skip the confirmation prompt
+        #[clap(short, long)]
+        force: bool,
+    },
+}
+
+/// Passkey as listed in the user settings
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+struct Passkey {
+    id: String,
+    name: String,
+    created_at: String,
+    used_at: Option<String>,
+}
+
+pub async fn handle_passkeys(client: &CloudreveClient, command: PasskeysCommands) -> Result<()> {
+    match command {
+        PasskeysCommands::List { json } => handle_list(client, json).await,
+        PasskeysCommands::Delete { ids, force } => handle_delete(client, ids, force).await,
+    }
+}
+
+async fn fetch_passkeys(client: &CloudreveClient) -> Result<Vec<Passkey>> {
+    let mut settings: Value = envelope::data(client.get("/user/setting").await?)?;
+    match settings.get_mut("passkeys").map(Value::take) {
+        Some(Value::Null) | None => Ok(Vec::new()),
+        Some(passkeys) => Ok(serde_json::from_value(passkeys)?),
+    }
+}
+
+async fn handle_list(client: &CloudreveClient, json: bool) -> Result<()> {
+    let passkeys = fetch_passkeys(client).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&passkeys)?);
+        return Ok(());
+    }
+
+    if passkeys.is_empty() {
+        info!("No passkeys registered");
+        return Ok(());
+    }
+
+    info!("Passkeys ({}):", passkeys.len());
+    for passkey in &passkeys {
+        info!("  - {} (ID: {})", passkey.name, passkey.id);
+        info!("      Created: {}", passkey.created_at);
+        info!(
+            "      Last used: {}",
+            passkey.used_at.as_deref().unwrap_or("never")
+        );
+    }
+
+    Ok(())
+}
+
+async fn handle_delete(client: &CloudreveClient, ids: Vec<String>, force: bool) -> Result<()> {
+    let passkeys = fetch_passkeys(client).await?;
+    let mut selected = Vec::new();
+    for id in &ids {
+        let passkey = passkeys
+            .iter()
+            .find(|p| &p.id == id)
+            .ok_or_else(|| Error::InvalidResponse(format!("Passkey not found: {}", id)))?;
+        selected.push(passkey);
+    }
+
+    info!("Passkeys to delete: {}", selected.len());
+    for passkey in &selected {
+        info!("  - {} (ID: {})", passkey.name, passkey.id);
+    }
+
+    // Removing the last passkey can lock out passwordless accounts, so ask first
+    if !force {
+        print!("Delete {} passkey(s)? [y/N]: ", selected.len());
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            info!("Operation cancelled");
+            return Ok(());
+        }
+    }
+
+    for passkey in selected {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        query.append_pair("id", &passkey.id);
+        envelope::check(
+            client
+                .delete(&format!("/user/authn?{}", query.finish()))
+                .await?,
+        )?;
+        info!("Deleted passkey: {}", passkey.name);
+    }
+
+    Ok(())
+}
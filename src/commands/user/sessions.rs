@@ -0,0 +1,262 @@
+use crate::utils::{envelope, time};
+use chrono::{DateTime, Utc};
+use cloudreve_api::{CloudreveClient, Error, Result};
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Reverse;
+
+/// Filters and output options of `user sessions`
+#[derive(clap::Args)]
+pub struct SessionsOptions {
+    /// Only failed logins
+    #[clap(long, conflicts_with = "success")]
+    pub failed: bool,
+
+    /// Only successful logins
+    #[clap(long)]
+    pub success: bool,
+
+    /// Only logins from this IP address
+    #[clap(long)]
+    pub ip: Option<String>,
+
+    /// Only logins after this time (YYYY-MM-DD, RFC 3339 or an age like 7d)
+    #[clap(long)]
+    pub since: Option<String>,
+
+    /// Show at most this many entries, newest first
+    #[clap(short = 'n', long)]
+    pub limit: Option<usize>,
+
+    /// Print entries as JSON
+    #[clap(long)]
+    pub json: bool,
+
+    /// Fail when failed logins within --alert-window exceed this count
+    #[clap(long)]
+    pub alert_threshold: Option<usize>,
+
+    /// Window for --alert-threshold
+    #[clap(long, default_value = "24h", requires = "alert_threshold")]
+    pub alert_window: String,
+}
+
+/// One login attempt from the user settings
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+struct LoginActivity {
+    created_at: String,
+    ip: String,
+    browser: String,
+    device: String,
+    os: String,
+    login_with: String,
+    success: bool,
+    webdav: bool,
+}
+
+impl LoginActivity {
+    fn time(&self) -> Option<DateTime<Utc>> {
+        time::parse_remote_time(&self.created_at)
+    }
+}
+
+/// Newest first, entries without a readable time last
+fn sort_newest_first(activities: &mut [LoginActivity]) {
+    activities.sort_by_key(|a| Reverse(a.time()));
+}
+
+/// Entries matching the filters, at most `--limit` of them
+fn select<'a>(
+    activities: &'a [LoginActivity],
+    options: &SessionsOptions,
+    since: Option<DateTime<Utc>>,
+) -> Vec<&'a LoginActivity> {
+    activities
+        .iter()
+        .filter(|a| !options.failed || !a.success)
+        .filter(|a| !options.success || a.success)
+        .filter(|a| options.ip.as_ref().is_none_or(|ip| &a.ip == ip))
+        .filter(|a| since.is_none_or(|since| a.time().is_some_and(|t| t >= since)))
+        .take(options.limit.unwrap_or(usize::MAX))
+        .collect()
+}
+
+/// Number of failed logins since `cutoff` when it is above `threshold`
+fn failed_login_alert(
+    activities: &[LoginActivity],
+    threshold: usize,
+    cutoff: DateTime<Utc>,
+) -> Option<usize> {
+    let failed = activities
+        .iter()
+        .filter(|a| !a.success)
+        .filter(|a| a.time().is_some_and(|t| t >= cutoff))
+        .count();
+    (failed > threshold).then_some(failed)
+}
+
+pub async fn handle_sessions(client: &CloudreveClient, options: SessionsOptions) -> Result<()> {
+    let mut settings: Value = envelope::data(client.get("/user/setting").await?)?;
+    let mut activities: Vec<LoginActivity> =
+        match settings.get_mut("login_activity").map(Value::take) {
+            Some(Value::Null) | None => Vec::new(),
+            Some(activities) => serde_json::from_value(activities)?,
+        };
+    sort_newest_first(&mut activities);
+
+    // The alert looks at every failed login, not just the filtered ones
+    let alert = match options.alert_threshold {
+        Some(threshold) => {
+            let window =
+                time::parse_duration(&options.alert_window).map_err(Error::InvalidResponse)?;
+            failed_login_alert(&activities, threshold, Utc::now() - window)
+                .map(|failed| (failed, threshold))
+        }
+        None => None,
+    };
+
+    let since = options
+        .since
+        .as_deref()
+        .map(time::parse_time_spec)
+        .transpose()
+        .map_err(Error::InvalidResponse)?;
+    let selected = select(&activities, &options, since);
+
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(&selected)?);
+    } else if selected.is_empty() {
+        info!("No login activity found");
+    } else {
+        info!("Login activity ({}):", selected.len());
+        for activity in &selected {
+            info!(
+                "  {} {} {} from {} ({}{}{})",
+                if activity.success { "✓" } else { "✗" },
+                activity.created_at,
+                activity.browser,
+                activity.ip,
+                activity.os,
+                if activity.device.is_empty() { "" } else { ", " },
+                activity.device
+            );
+            if !activity.login_with.is_empty() || activity.webdav {
+                info!(
+                    "      via {}",
+                    if activity.webdav {
+                        "WebDAV"
+                    } else {
+                        activity.login_with.as_str()
+                    }
+                );
+            }
+        }
+    }
+
+    if let Some((failed, threshold)) = alert {
+        return Err(Error::InvalidResponse(format!(
+            "Failed login alert: {} failed login(s) in the last {}, above the threshold of {}",
+            failed, options.alert_window, threshold
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn activity(hours_ago: i64, ip: &str, success: bool) -> LoginActivity {
+        LoginActivity {
+            created_at: (Utc::now() - Duration::hours(hours_ago)).to_rfc3339(),
+            ip: ip.to_string(),
+            success,
+            ..Default::default()
+        }
+    }
+
+    fn options() -> SessionsOptions {
+        SessionsOptions {
+            failed: false,
+            success: false,
+            ip: None,
+            since: None,
+            limit: None,
+            json: false,
+            alert_threshold: None,
+            alert_window: "24h".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sort_and_select() {
+        let mut activities = vec![
+            activity(48, "10.0.0.1", true),
+            LoginActivity {
+                created_at: "garbage".to_string(),
+                ..Default::default()
+            },
+            activity(1, "10.0.0.2", false),
+            // 时区不同的时间按实际时刻排序，而不是按字符串
+            LoginActivity {
+                created_at: (Utc::now() - Duration::hours(2))
+                    .with_timezone(&chrono::FixedOffset::east_opt(8 * 3600).unwrap())
+                    .to_rfc3339(),
+                ip: "10.0.0.1".to_string(),
+                success: false,
+                ..Default::default()
+            },
+        ];
+        sort_newest_first(&mut activities);
+        let ips: Vec<&str> = activities.iter().map(|a| a.ip.as_str()).collect();
+        assert_eq!(ips, ["10.0.0.2", "10.0.0.1", "10.0.0.1", ""]);
+
+        let failed = SessionsOptions {
+            failed: true,
+            ..options()
+        };
+        assert_eq!(select(&activities, &failed, None).len(), 3);
+
+        let success = SessionsOptions {
+            success: true,
+            ..options()
+        };
+        assert_eq!(select(&activities, &success, None).len(), 1);
+
+        let ip = SessionsOptions {
+            ip: Some("10.0.0.1".to_string()),
+            limit: Some(1),
+            ..options()
+        };
+        let selected = select(&activities, &ip, None);
+        assert_eq!(selected.len(), 1);
+        assert!(!selected[0].success);
+
+        // --since 排除更早和时间无法解析的记录
+        let since = Some(Utc::now() - Duration::hours(24));
+        assert_eq!(select(&activities, &options(), since).len(), 2);
+    }
+
+    #[test]
+    fn test_failed_login_alert() {
+        let activities = vec![
+            activity(1, "10.0.0.1", false),
+            activity(2, "10.0.0.1", false),
+            activity(3, "10.0.0.1", true),
+            activity(30, "10.0.0.1", false),
+        ];
+        let cutoff = Utc::now() - Duration::hours(24);
+
+        // 窗口内只有 2 次失败，窗口外的不计入
+        assert_eq!(failed_login_alert(&activities, 1, cutoff), Some(2));
+        assert_eq!(failed_login_alert(&activities, 2, cutoff), None);
+        assert_eq!(
+            failed_login_alert(&activities, 2, Utc::now() - Duration::hours(48)),
+            Some(3)
+        );
+    }
+}
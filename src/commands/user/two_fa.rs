@@ -0,0 +1,116 @@
+use crate::context::TokenManager;
+use crate::utils::{credentials, envelope};
+use cloudreve_api::{CloudreveAPI, CloudreveClient, Result};
+use log::info;
+use serde_json::json;
+use std::io::{self, IsTerminal};
+
+#[derive(clap::Subcommand)]
+pub enum TwoFaCommands {
+    /// Show whether two-factor authentication is enabled
+    Status,
+
+    /// Enable two-factor authentication
+    ///
+    /// Without --otp a new secret is generated and shown; on a terminal you are
+    /// then asked for a code, otherwise re-run with --otp to confirm.
+    Enable {
+        /// TOTP code from your authenticator app
+        #[clap(long)]
+        otp: Option<String>,
+    },
+
+    /// Disable two-factor authentication
+    Disable {
+        /// TOTP code from your authenticator app
+        #[clap(long)]
+        otp: Option<String>,
+    },
+}
+
+pub async fn handle_two_fa(
+    api: &CloudreveAPI,
+    client: &CloudreveClient,
+    token_manager: &TokenManager,
+    command: TwoFaCommands,
+) -> Result<()> {
+    match command {
+        TwoFaCommands::Status => {
+            let settings = client.get_settings().await?;
+            info!("2FA Enabled: {}", settings.two_fa_enabled);
+            Ok(())
+        }
+        TwoFaCommands::Enable { otp } => handle_enable(api, client, token_manager, otp).await,
+        TwoFaCommands::Disable { otp } => {
+            info!("Disabling two-factor authentication...");
+            let code = credentials::resolve_otp(otp)?;
+            set_two_fa(client, false, &code).await?;
+            info!("Two-factor authentication disabled");
+            Ok(())
+        }
+    }
+}
+
+async fn handle_enable(
+    api: &CloudreveAPI,
+    client: &CloudreveClient,
+    token_manager: &TokenManager,
+    otp: Option<String>,
+) -> Result<()> {
+    // A code given up front confirms the secret generated by a previous run
+    if let Some(code) = otp {
+        let code = credentials::resolve_otp(Some(code))?;
+        set_two_fa(client, true, &code).await?;
+        info!("Two-factor authentication enabled");
+        return Ok(());
+    }
+
+    info!("Generating two-factor authentication secret...");
+    let secret: String = envelope::data(client.get("/user/setting/2fa").await?)?;
+
+    let account = token_manager
+        .get_token_by_url(api.base_url())?
+        .map(|t| t.email)
+        .unwrap_or_else(|| "cloudreve".to_string());
+    let issuer = url::Url::parse(api.base_url())
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_string()))
+        .unwrap_or_else(|| "Cloudreve".to_string());
+
+    info!("");
+    info!("Add this account to your authenticator app:");
+    info!("  Secret: {}", secret);
+    info!("  URI:    {}", provisioning_uri(&secret, &account, &issuer));
+    info!("");
+
+    if !io::stdin().is_terminal() {
+        info!("Confirm with the code shown by your authenticator app:");
+        info!("  cloudreve-cli user 2fa enable --otp <code>");
+        return Ok(());
+    }
+
+    let code = credentials::resolve_otp(None)?;
+    set_two_fa(client, true, &code).await?;
+    info!("Two-factor authentication enabled");
+    Ok(())
+}
+
+async fn set_two_fa(client: &CloudreveClient, enabled: bool, code: &str) -> Result<()> {
+    let request = json!({
+        "two_fa_enabled": enabled,
+        "two_fa_code": code,
+    });
+    envelope::check(client.patch("/user/setting", &request).await?)
+}
+
+/// Builds an `otpauth://` URI understood by authenticator apps
+fn provisioning_uri(secret: &str, account: &str, issuer: &str) -> String {
+    let encode = |s: &str| url::form_urlencoded::byte_serialize(s.as_bytes()).collect::<String>();
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}",
+        encode(issuer),
+        encode(account),
+        secret,
+        encode(issuer)
+    )
+}
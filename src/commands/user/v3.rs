@@ -0,0 +1,89 @@
+//! Profile, password and policy commands on V3 servers
+
+use crate::utils::envelope;
+use cloudreve_api::{CloudreveAPI, Error, Result, UnifiedClient};
+use log::info;
+use serde_json::{Value, json};
+
+pub async fn handle_policies(api: &CloudreveAPI) -> Result<()> {
+    let UnifiedClient::V3(client) = api.inner() else {
+        return Err(Error::InvalidResponse("V3 client required".to_string()));
+    };
+
+    info!("Fetching available storage policies...");
+    let policies: Option<Vec<Value>> = envelope::data(client.get("/user/setting/policies").await?)?;
+    let policies = policies.unwrap_or_default();
+
+    if policies.is_empty() {
+        info!("No storage policies available.");
+        return Ok(());
+    }
+
+    info!("Available storage policies:");
+    info!("");
+    for (index, policy) in policies.iter().enumerate() {
+        info!(
+            "  [{}] {} (ID: {})",
+            index,
+            policy["name"].as_str().unwrap_or("-"),
+            policy["id"]
+        );
+        if let Some(type_) = policy["type"].as_str() {
+            info!("      Type: {}", type_);
+        }
+        if let Some(max_size) = policy["max_size"].as_i64() {
+            info!("      Max Size: {} MB", max_size / 1024 / 1024);
+        }
+        info!("");
+    }
+
+    Ok(())
+}
+
+pub async fn handle_update_profile(
+    api: &CloudreveAPI,
+    nickname: Option<String>,
+    avatar: Option<String>,
+) -> Result<()> {
+    let UnifiedClient::V3(client) = api.inner() else {
+        return Err(Error::InvalidResponse("V3 client required".to_string()));
+    };
+
+    if avatar.is_some() {
+        return Err(Error::InvalidResponse(
+            "--avatar is not supported on V3 servers, upload the avatar in the web UI".to_string(),
+        ));
+    }
+    let Some(nickname) = nickname else {
+        return Err(Error::InvalidResponse(
+            "Nothing to update, pass --nickname".to_string(),
+        ));
+    };
+
+    info!("Updating user profile...");
+    envelope::check(
+        client
+            .patch("/user/setting/nick", &json!({ "nick": nickname }))
+            .await?,
+    )?;
+
+    info!("Profile updated successfully");
+    Ok(())
+}
+
+pub async fn handle_change_password(
+    api: &CloudreveAPI,
+    old_password: String,
+    new_password: String,
+) -> Result<()> {
+    let UnifiedClient::V3(client) = api.inner() else {
+        return Err(Error::InvalidResponse("V3 client required".to_string()));
+    };
+
+    info!("Changing password...");
+    let request = json!({ "old": old_password, "new": new_password });
+    envelope::check(client.patch("/user/setting/password", &request).await?)?;
+
+    info!("Password changed successfully");
+    Ok(())
+}
@@ -8,6 +8,7 @@ pub mod import;
 pub mod list;
 pub mod progress;
 pub mod relocate;
+pub mod v3;
 
 use cloudreve_api::{CloudreveAPI, Result, UnifiedClient};
 use log::debug;
@@ -172,8 +173,6 @@ pub async fn handle_workflow_command(api: &CloudreveAPI, command: WorkflowComman
                 download::handle_download_command(client, command).await
             }
         },
-        UnifiedClient::V3(_) => Err(cloudreve_api::Error::InvalidResponse(
-            "Workflow 命令仅支持 V4 API".to_string(),
-        )),
+        UnifiedClient::V3(_) => v3::handle_workflow_command(api, command).await,
     }
 }
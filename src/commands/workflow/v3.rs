@@ -0,0 +1,329 @@
+// V3 服务端的 Workflow 命令：离线下载（aria2）、压缩与解压任务
+
+use super::WorkflowCommands;
+use super::download::DownloadCommands;
+use crate::utils::envelope;
+use cloudreve_api::{ApiV3Client, CloudreveAPI, Error, Result, UnifiedClient};
+use log::info;
+use serde_json::{Value, json};
+
+/// V3 不支持的选项统一报错
+fn unsupported(what: &str) -> Error {
+    Error::InvalidResponse(format!("{} is not supported on V3 servers", what))
+}
+
+pub async fn handle_workflow_command(api: &CloudreveAPI, command: WorkflowCommands) -> Result<()> {
+    match command {
+        WorkflowCommands::List { category, per_page } => handle_list(api, category, per_page).await,
+        // V3 的普通任务（压缩、解压、中转）无法取消
+        WorkflowCommands::Cancel { .. } => Err(Error::InvalidResponse(
+            "General tasks cannot be cancelled on V3 servers; cancel offline downloads with `workflow download cancel <gid>`".to_string(),
+        )),
+        WorkflowCommands::Archive { files, name, path } => {
+            handle_archive(api, files, name, path).await
+        }
+        WorkflowCommands::Extract { archive, path } => handle_extract(api, archive, path).await,
+        WorkflowCommands::Download { command } => match command {
+            DownloadCommands::Create { url, path, node } => {
+                if node.is_some() {
+                    return Err(unsupported("--node"));
+                }
+                create_download(api, url, path).await
+            }
+            DownloadCommands::Select { task, files } => select_files(api, task, files).await,
+            DownloadCommands::Cancel { task } => cancel_download(api, task).await,
+        },
+        WorkflowCommands::Progress { .. } => Err(unsupported(
+            "`workflow progress` (use `workflow list` for task status)",
+        )),
+        WorkflowCommands::Relocate { .. } => Err(unsupported("`workflow relocate`")),
+        WorkflowCommands::Import { .. } => Err(unsupported("`workflow import`")),
+    }
+}
+
+async fn handle_list(api: &CloudreveAPI, category: String, per_page: String) -> Result<()> {
+    let UnifiedClient::V3(client) = api.inner() else {
+        return Err(Error::InvalidResponse("V3 client required".to_string()));
+    };
+    info!("Listing workflow tasks (category: {})...", category);
+
+    // V3 的任务接口不支持自定义分页大小
+    if per_page != "25" {
+        info!("Note: --per-page is ignored on V3 servers");
+    }
+
+    match category.as_str() {
+        "general" => {
+            let tasks = fetch_general_tasks(client).await?;
+            if tasks.is_empty() {
+                info!("No tasks found");
+                return Ok(());
+            }
+            info!("Tasks ({}):", tasks.len());
+            for task in &tasks {
+                info!(
+                    "  - [{}] {} {} ({})",
+                    task_type(&task["type"]),
+                    task_status(&task["status"]),
+                    text(&task["create_date"]),
+                    text(&task["progress"])
+                );
+                if let Some(error) = task["error"].as_str().filter(|e| !e.is_empty()) {
+                    info!("      Error: {}", error);
+                }
+            }
+        }
+        "downloading" | "downloaded" => {
+            let tasks = if category == "downloading" {
+                envelope::data::<Option<Vec<Value>>>(client.get("/aria2/downloading").await?)?
+                    .unwrap_or_default()
+            } else {
+                fetch_finished_downloads(client).await?
+            };
+            if tasks.is_empty() {
+                info!("No tasks found");
+                return Ok(());
+            }
+            info!("Download tasks ({}):", tasks.len());
+            for task in &tasks {
+                info!(
+                    "  - {} {} -> {}",
+                    text(&task["info"]["gid"]),
+                    text(&task["name"]),
+                    text(&task["dst"])
+                );
+                let total = task["total"].as_i64().unwrap_or(0);
+                if total > 0 {
+                    let downloaded = task["downloaded"].as_i64().unwrap_or(0);
+                    info!(
+                        "      {} / {} ({:.1}%)",
+                        crate::utils::format_bytes(downloaded),
+                        crate::utils::format_bytes(total),
+                        downloaded as f64 * 100.0 / total as f64
+                    );
+                }
+            }
+        }
+        other => {
+            return Err(Error::InvalidResponse(format!(
+                "Unknown category: {} (use general, downloading or downloaded)",
+                other
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// 逐页读取全部普通任务
+async fn fetch_general_tasks(client: &ApiV3Client) -> Result<Vec<Value>> {
+    let mut tasks = Vec::new();
+    for page in 1.. {
+        let response: Value = envelope::data(
+            client
+                .get(&format!("/user/setting/tasks?page={}", page))
+                .await?,
+        )?;
+        let items = response["tasks"].as_array().cloned().unwrap_or_default();
+        let total = response["total"].as_u64().unwrap_or(0) as usize;
+        if items.is_empty() {
+            break;
+        }
+        tasks.extend(items);
+        if tasks.len() >= total {
+            break;
+        }
+    }
+    Ok(tasks)
+}
+
+/// 逐页读取全部已完成的离线下载，接口不返回总数，读到空页为止
+async fn fetch_finished_downloads(client: &ApiV3Client) -> Result<Vec<Value>> {
+    let mut tasks = Vec::new();
+    for page in 1.. {
+        let items: Option<Vec<Value>> = envelope::data(
+            client
+                .get(&format!("/aria2/finished?page={}", page))
+                .await?,
+        )?;
+        let items = items.unwrap_or_default();
+        if items.is_empty() {
+            break;
+        }
+        tasks.extend(items);
+    }
+    Ok(tasks)
+}
+
+async fn create_download(api: &CloudreveAPI, url: String, path: Option<String>) -> Result<()> {
+    let UnifiedClient::V3(client) = api.inner() else {
+        return Err(Error::InvalidResponse("V3 client required".to_string()));
+    };
+    let dst = path.unwrap_or_else(|| "/".to_string());
+    info!("Creating download task: {}", url);
+
+    envelope::check(
+        client
+            .post("/aria2/url", &json!({ "url": url, "dst": dst }))
+            .await?,
+    )?;
+
+    info!("");
+    info!("✅ Download task created successfully");
+    info!("  URL: {}", url);
+    info!("  Destination: {}", dst);
+    info!("");
+    info!("💡 Find the task ID (GID) with:");
+    info!("   cloudreve-cli workflow list --category downloading");
+
+    Ok(())
+}
+
+async fn select_files(api: &CloudreveAPI, task_id: String, files: String) -> Result<()> {
+    let UnifiedClient::V3(client) = api.inner() else {
+        return Err(Error::InvalidResponse("V3 client required".to_string()));
+    };
+    info!("Selecting files for task {}: {}", task_id, files);
+
+    // V3 按 BT 任务中的文件序号选择
+    let indexes = files
+        .split(',')
+        .map(|s| s.trim().parse::<i64>())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|_| {
+            Error::InvalidResponse("V3 servers select files by index, e.g. -f 1,2,5".to_string())
+        })?;
+
+    envelope::check(
+        client
+            .put(
+                &format!("/aria2/select/{}", task_id),
+                &json!({ "indexes": indexes }),
+            )
+            .await?,
+    )?;
+
+    info!("✅ Files selected successfully");
+    Ok(())
+}
+
+async fn cancel_download(api: &CloudreveAPI, task_id: String) -> Result<()> {
+    let UnifiedClient::V3(client) = api.inner() else {
+        return Err(Error::InvalidResponse("V3 client required".to_string()));
+    };
+    info!("Canceling download task: {}", task_id);
+
+    envelope::check(client.delete(&format!("/aria2/task/{}", task_id)).await?)?;
+
+    info!("✅ Download task {} canceled successfully", task_id);
+    Ok(())
+}
+
+async fn handle_archive(
+    api: &CloudreveAPI,
+    files: String,
+    name: String,
+    path: Option<String>,
+) -> Result<()> {
+    let UnifiedClient::V3(client) = api.inner() else {
+        return Err(Error::InvalidResponse("V3 client required".to_string()));
+    };
+    info!("Creating archive '{}' from files: {}", name, files);
+
+    // V3 压缩接口按对象 ID 指定源文件，需要先在父目录中查找
+    let mut dirs = Vec::new();
+    let mut items = Vec::new();
+    for file in files.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        let (parent, file_name) = match file.trim_end_matches('/').rsplit_once('/') {
+            Some(("", name)) => ("/", name),
+            Some((parent, name)) => (parent, name),
+            None => ("/", file),
+        };
+
+        let listing: Value = envelope::data(client.get(&directory_path(parent)).await?)?;
+        let object = listing["objects"]
+            .as_array()
+            .and_then(|objects| objects.iter().find(|o| o["name"] == file_name))
+            .ok_or_else(|| Error::InvalidResponse(format!("File not found: {}", file)))?;
+
+        let id = object["id"].clone();
+        if object["type"] == "dir" {
+            dirs.push(id);
+        } else {
+            items.push(id);
+        }
+    }
+
+    let dst = path.unwrap_or_else(|| "/".to_string());
+    let request = json!({
+        "src": { "dirs": dirs, "items": items },
+        "name": name,
+        "dst": dst,
+    });
+    envelope::check(client.post("/file/compress", &request).await?)?;
+
+    info!("");
+    info!("✅ Archive task created successfully");
+    info!("  Destination: {}", dst);
+    info!("  Check progress with: cloudreve-cli workflow list");
+
+    Ok(())
+}
+
+async fn handle_extract(api: &CloudreveAPI, archive: String, path: Option<String>) -> Result<()> {
+    let UnifiedClient::V3(client) = api.inner() else {
+        return Err(Error::InvalidResponse("V3 client required".to_string()));
+    };
+    info!("Extracting archive: {}", archive);
+
+    let dst = path.unwrap_or_else(|| "/".to_string());
+    let request = json!({ "src": archive, "dst": dst, "encoding": "" });
+    envelope::check(client.post("/file/decompress", &request).await?)?;
+
+    info!("");
+    info!("✅ Extract task created successfully");
+    info!("  Destination: {}", dst);
+    info!("  Check progress with: cloudreve-cli workflow list");
+
+    Ok(())
+}
+
+/// `/directory/<path>` 接口路径，逐段编码
+fn directory_path(path: &str) -> String {
+    let mut url = url::Url::parse("http://localhost/directory").expect("static URL");
+    if let Ok(mut segments) = url.path_segments_mut() {
+        segments.extend(path.split('/').filter(|s| !s.is_empty()));
+    }
+    url.path().to_string()
+}
+
+/// V3 任务类型编号
+fn task_type(value: &Value) -> &'static str {
+    match value.as_i64() {
+        Some(0) => "compress",
+        Some(1) => "decompress",
+        Some(2) => "transfer",
+        Some(3) => "import",
+        _ => "other",
+    }
+}
+
+/// V3 任务状态编号
+fn task_status(value: &Value) -> &'static str {
+    match value.as_i64() {
+        Some(0) => "⏳ queued",
+        Some(1) => "🔄 processing",
+        Some(2) => "❌ error",
+        Some(3) => "🚫 canceled",
+        Some(4) => "✅ completed",
+        _ => "unknown",
+    }
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
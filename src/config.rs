@@ -1,9 +1,35 @@
+//! Layered CLI configuration
+//!
+//! Values are resolved from, lowest to highest precedence: built-in defaults,
+//! the global file (`~/.config/cloudreve-cli/config.toml`), a project-local
+//! `.cloudreve.toml` found in the current directory or one of its parents,
+//! and `CLOUDREVE_<KEY>` environment variables. Command-line flags are
+//! applied on top by the caller.
+
 use dirs::home_dir;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// File name of the project-local configuration
+pub const LOCAL_CONFIG_FILE: &str = ".cloudreve.toml";
+
+/// Prefix of environment variables overriding configuration keys
+pub const ENV_PREFIX: &str = "CLOUDREVE_";
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Keys deciding where requests and credentials go. A project-local file may
+/// come from a cloned repository, so these are only read from the global file
+/// and the environment.
+pub const CONNECTION_KEYS: [&str; 6] = [
+    "default_url",
+    "proxy",
+    "ca_cert",
+    "client_cert",
+    "client_key",
+    "insecure",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub default_url: Option<String>,
     pub default_email: Option<String>,
@@ -11,6 +37,16 @@ pub struct Config {
     pub default_upload_path: Option<String>,
     pub default_download_dir: Option<String>,
     pub log_level: Option<String>,
+    pub proxy: Option<String>,
+    pub ca_cert: Option<String>,
+    pub client_cert: Option<String>,
+    pub client_key: Option<String>,
+    pub insecure: Option<bool>,
+    pub timeout: Option<u64>,
+    pub connect_timeout: Option<u64>,
+    pub pool_max_idle_per_host: Option<usize>,
+    pub pool_idle_timeout: Option<u64>,
+    pub http1_only: Option<bool>,
 }
 
 impl Default for Config {
@@ -22,37 +58,250 @@ impl Default for Config {
             default_upload_path: Some("/".to_string()),
             default_download_dir: Some(".".to_string()),
             log_level: Some("info".to_string()),
+            proxy: None,
+            ca_cert: None,
+            client_cert: None,
+            client_key: None,
+            insecure: None,
+            timeout: None,
+            connect_timeout: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            http1_only: None,
+        }
+    }
+}
+
+/// Where a configuration layer comes from
+#[derive(Debug, Clone)]
+pub enum ConfigSource {
+    Default,
+    Global(PathBuf),
+    Local(PathBuf),
+    Env,
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::Global(path) | ConfigSource::Local(path) => {
+                write!(f, "{}", path.display())
+            }
+            ConfigSource::Env => write!(f, "env"),
         }
     }
 }
 
 impl Config {
+    /// Creates a configuration with every key unset
+    pub fn empty() -> Self {
+        Config {
+            default_url: None,
+            default_email: None,
+            default_policy: None,
+            default_upload_path: None,
+            default_download_dir: None,
+            log_level: None,
+            proxy: None,
+            ca_cert: None,
+            client_cert: None,
+            client_key: None,
+            insecure: None,
+            timeout: None,
+            connect_timeout: None,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            http1_only: None,
+        }
+    }
+
+    /// Loads the effective configuration from all layers
     pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        let config_path = Self::config_file_path()?;
+        let mut config = Self::empty();
+        for (_, layer) in Self::load_layers()? {
+            config.merge(layer);
+        }
+        Ok(config)
+    }
+
+    /// Loads every configuration layer, lowest precedence first
+    pub fn load_layers() -> Result<Vec<(ConfigSource, Config)>, Box<dyn std::error::Error>> {
+        let mut layers = vec![(ConfigSource::Default, Self::default())];
 
-        if !config_path.exists() {
-            return Ok(Self::default());
+        let global_path = Self::config_file_path()?;
+        if global_path.exists() {
+            layers.push((
+                ConfigSource::Global(global_path.clone()),
+                Self::load_file(&global_path)?,
+            ));
         }
 
-        let content = fs::read_to_string(&config_path)?;
+        if let Some(local_path) = Self::local_config_path() {
+            let mut local = Self::load_file(&local_path)?;
+            let ignored = local.strip_connection_keys();
+            if !ignored.is_empty() {
+                // Logging is not set up yet, the log level itself comes from these layers
+                eprintln!(
+                    "Warning: ignoring {} in {}; connection settings are only read from the global config or environment",
+                    ignored.join(", "),
+                    local_path.display()
+                );
+            }
+            layers.push((ConfigSource::Local(local_path), local));
+        }
+
+        layers.push((ConfigSource::Env, Self::from_env()?));
+
+        Ok(layers)
+    }
+
+    /// Loads a single configuration file, missing keys stay unset
+    pub fn load_file(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(path)?;
         let config: Config = toml::from_str(&content)?;
         Ok(config)
     }
 
-    pub fn _save(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let config_path = Self::config_file_path()?;
-
+    /// Writes this configuration to the given file
+    pub fn save_to(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
         // Ensure config directory exists
-        if let Some(parent) = config_path.parent() {
+        if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
 
         let content = toml::to_string_pretty(self)?;
-        fs::write(&config_path, content)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Builds a layer from `CLOUDREVE_<KEY>` environment variables
+    fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let mut config = Self::empty();
+        for key in Self::keys() {
+            let var = format!("{}{}", ENV_PREFIX, key.to_uppercase());
+            if let Ok(value) = std::env::var(&var)
+                && !value.is_empty()
+            {
+                config
+                    .set(key, &value)
+                    .map_err(|e| format!("Invalid value in {}: {}", var, e))?;
+            }
+        }
+        Ok(config)
+    }
+
+    /// Overrides keys with every value set in `other`
+    pub fn merge(&mut self, other: Config) {
+        let mut table = self.to_table();
+        table.extend(other.to_table());
+        if let Ok(merged) = toml::Value::Table(table).try_into() {
+            *self = merged;
+        }
+    }
+
+    /// Names of all configuration keys
+    pub fn keys() -> Vec<&'static str> {
+        vec![
+            "default_url",
+            "default_email",
+            "default_policy",
+            "default_upload_path",
+            "default_download_dir",
+            "log_level",
+            "proxy",
+            "ca_cert",
+            "client_cert",
+            "client_key",
+            "insecure",
+            "timeout",
+            "connect_timeout",
+            "pool_max_idle_per_host",
+            "pool_idle_timeout",
+            "http1_only",
+        ]
+    }
+
+    /// Gets a key's value as a display string
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.to_table().get(key).map(|value| match value {
+            toml::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+    }
+
+    /// Sets a key, parsing the value into the key's type
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        Self::check_key(key)?;
+
+        // Try the typed interpretation first, then fall back to a plain string
+        let mut candidates = Vec::new();
+        if let Ok(b) = value.parse::<bool>() {
+            candidates.push(toml::Value::Boolean(b));
+        }
+        if let Ok(i) = value.parse::<i64>() {
+            candidates.push(toml::Value::Integer(i));
+        }
+        candidates.push(toml::Value::String(value.to_string()));
+
+        for candidate in candidates {
+            let mut table = self.to_table();
+            table.insert(key.to_string(), candidate);
+            if let Ok(updated) = toml::Value::Table(table).try_into() {
+                *self = updated;
+                return Ok(());
+            }
+        }
+
+        Err(format!("Invalid value for {}: {}", key, value))
+    }
+
+    /// Removes a key
+    pub fn unset(&mut self, key: &str) -> Result<(), String> {
+        Self::check_key(key)?;
+
+        let mut table = self.to_table();
+        table.remove(key);
+        *self = toml::Value::Table(table)
+            .try_into()
+            .map_err(|e| format!("Failed to unset {}: {}", key, e))?;
         Ok(())
     }
 
-    fn config_file_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    /// Removes the connection keys, returning the ones that were set
+    pub fn strip_connection_keys(&mut self) -> Vec<&'static str> {
+        let mut table = self.to_table();
+        let removed: Vec<&'static str> = CONNECTION_KEYS
+            .into_iter()
+            .filter(|key| table.remove(*key).is_some())
+            .collect();
+        if let Ok(stripped) = toml::Value::Table(table).try_into() {
+            *self = stripped;
+        }
+        removed
+    }
+
+    fn check_key(key: &str) -> Result<(), String> {
+        if Self::keys().contains(&key) {
+            Ok(())
+        } else {
+            Err(format!(
+                "Unknown config key: {}. Available keys: {}",
+                key,
+                Self::keys().join(", ")
+            ))
+        }
+    }
+
+    fn to_table(&self) -> toml::Table {
+        match toml::Value::try_from(self) {
+            Ok(toml::Value::Table(table)) => table,
+            _ => toml::Table::new(),
+        }
+    }
+
+    /// Path of the global configuration file
+    pub fn config_file_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
         let config_dir = home_dir()
             .ok_or("Cannot determine home directory")?
             .join(".config")
@@ -60,4 +309,68 @@ impl Config {
 
         Ok(config_dir.join("config.toml"))
     }
+
+    /// Finds `.cloudreve.toml` in the current directory or its ancestors
+    pub fn local_config_path() -> Option<PathBuf> {
+        let cwd = std::env::current_dir().ok()?;
+        cwd.ancestors()
+            .map(|dir| dir.join(LOCAL_CONFIG_FILE))
+            .find(|path| path.is_file())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_overrides_only_set_keys() {
+        let mut config = Config::default();
+        let mut layer = Config::empty();
+        layer.default_url = Some("https://example.com".to_string());
+
+        config.merge(layer);
+
+        assert_eq!(config.default_url.as_deref(), Some("https://example.com"));
+        assert_eq!(config.default_upload_path.as_deref(), Some("/"));
+        assert_eq!(config.log_level.as_deref(), Some("info"));
+    }
+
+    #[test]
+    fn test_set_get_unset() {
+        let mut config = Config::empty();
+
+        // 数字形式的字符串值仍按字符串保存
+        config.set("default_policy", "3").unwrap();
+        assert_eq!(config.default_policy.as_deref(), Some("3"));
+        assert_eq!(config.get("default_policy").as_deref(), Some("3"));
+
+        config.unset("default_policy").unwrap();
+        assert!(config.get("default_policy").is_none());
+
+        // 布尔和整数类型会被校验
+        config.set("insecure", "true").unwrap();
+        assert_eq!(config.insecure, Some(true));
+        config.set("timeout", "30").unwrap();
+        assert_eq!(config.timeout, Some(30));
+        assert!(config.set("timeout", "soon").is_err());
+
+        assert!(config.set("no_such_key", "1").is_err());
+    }
+
+    #[test]
+    fn test_strip_connection_keys() {
+        let mut config = Config::empty();
+        config
+            .set("default_url", "https://evil.example.com")
+            .unwrap();
+        config.set("proxy", "http://127.0.0.1:8080").unwrap();
+        config.set("default_policy", "2").unwrap();
+
+        // 项目本地配置不能改变请求目标
+        assert_eq!(config.strip_connection_keys(), vec!["default_url", "proxy"]);
+        assert!(config.default_url.is_none());
+        assert!(config.proxy.is_none());
+        assert_eq!(config.default_policy.as_deref(), Some("2"));
+    }
 }
@@ -1,3 +1,4 @@
+use super::http::{self, NetworkOptions, build_http_client};
 use super::token_manager::{TokenInfo as CliTokenInfo, TokenManager};
 use cloudreve_api::{ApiVersion, CloudreveAPI, Result, UnifiedClient};
 use log::{debug, info, warn};
@@ -7,17 +8,21 @@ pub struct ClientConfig {
     pub url: Option<String>,
     pub email: Option<String>,
     pub token: Option<String>,
+    pub network: NetworkOptions,
 }
 
 /// Client initialization result
 pub struct ClientContext {
     pub api: Option<CloudreveAPI>,
     pub token_manager: TokenManager,
+    /// Pooled HTTP client shared by all transfer and preview requests
+    pub http: reqwest::Client,
 }
 
 /// Initializes client and handles token management
 pub async fn initialize_client(config: ClientConfig) -> Result<ClientContext> {
     let token_manager = TokenManager::new()?;
+    let http = build_http_client(&config.network)?;
     let token_provided = config.token.is_some();
 
     let api = if !token_provided {
@@ -28,6 +33,7 @@ pub async fn initialize_client(config: ClientConfig) -> Result<ClientContext> {
             &token_manager,
             config.url.as_deref(),
             config.email.as_deref(),
+            &http,
         )
         .await?;
 
@@ -44,12 +50,16 @@ pub async fn initialize_client(config: ClientConfig) -> Result<ClientContext> {
     } else {
         // Token provided via command line
         let url = config.url.expect("URL is required when token is provided");
-        let mut api = CloudreveAPI::new(&url).await?;
+        let mut api = http::connect(&url, &http).await?;
         api.set_token(&config.token.unwrap())?;
         Some(api)
     };
 
-    Ok(ClientContext { api, token_manager })
+    Ok(ClientContext {
+        api,
+        token_manager,
+        http,
+    })
 }
 
 /// Attempts to load and refresh a cached token if needed
@@ -57,6 +67,7 @@ async fn load_and_refresh_token(
     token_manager: &TokenManager,
     url: Option<&str>,
     email: Option<&str>,
+    http: &reqwest::Client,
 ) -> Result<Option<(CliTokenInfo, CloudreveAPI)>> {
     // Load token from cache based on URL and/or email
     let token_info = match (url, email) {
@@ -133,7 +144,7 @@ async fn load_and_refresh_token(
         "Using cached API version: {:?} (skipping version detection)",
         api_version
     );
-    let mut api = CloudreveAPI::with_version(&cached_url, api_version)?;
+    let mut api = http::with_version(&cached_url, api_version, http)?;
 
     // V3 tokens don't have expiration info - use directly and let API errors handle auth
     if api_version == ApiVersion::V3 {
@@ -0,0 +1,148 @@
+//! Network options and the shared HTTP client
+//!
+//! API, transfer and preview requests share one pooled `reqwest::Client`
+//! built here and owned by `ClientContext`. API clients from `cloudreve-api`
+//! are created through `connect`/`with_version`, which install that client.
+
+use cloudreve_api::{ApiVersion, CloudreveAPI, Result, UnifiedClient};
+use log::debug;
+use reqwest::{Certificate, Client, Identity, Proxy};
+use std::fs;
+use std::time::Duration;
+
+/// Proxy, TLS and timeout settings
+#[derive(Debug, Clone, Default)]
+pub struct NetworkOptions {
+    /// HTTP(S) or SOCKS5 proxy URL
+    pub proxy: Option<String>,
+    /// Extra CA bundle (PEM) trusted in addition to the system roots
+    pub ca_cert: Option<String>,
+    /// Client certificate (PEM)
+    pub client_cert: Option<String>,
+    /// Private key for the client certificate (PKCS#8 PEM)
+    pub client_key: Option<String>,
+    /// Skip TLS certificate verification
+    pub insecure: bool,
+    /// Seconds a request may wait for data, so long transfers are not cut off
+    pub timeout: Option<u64>,
+    /// Connection timeout in seconds
+    pub connect_timeout: Option<u64>,
+    /// Maximum idle connections kept per host
+    pub pool_max_idle_per_host: Option<usize>,
+    /// Seconds an idle pooled connection is kept alive
+    pub pool_idle_timeout: Option<u64>,
+    /// Disable HTTP/2 (for proxies that mishandle it)
+    pub http1_only: bool,
+}
+
+/// Builds an HTTP client from network options
+pub fn build_http_client(options: &NetworkOptions) -> Result<Client> {
+    let mut builder =
+        Client::builder().user_agent(concat!("cloudreve-cli/", env!("CARGO_PKG_VERSION")));
+
+    if let Some(proxy) = &options.proxy {
+        builder = builder.proxy(Proxy::all(proxy)?);
+    }
+
+    if let Some(path) = &options.ca_cert {
+        let pem = fs::read(path)?;
+        for cert in Certificate::from_pem_bundle(&pem)? {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+
+    match (&options.client_cert, &options.client_key) {
+        (Some(cert_path), Some(key_path)) => {
+            let identity = Identity::from_pkcs8_pem(&fs::read(cert_path)?, &fs::read(key_path)?)?;
+            builder = builder.identity(identity);
+        }
+        (Some(_), None) | (None, Some(_)) => {
+            return Err(cloudreve_api::Error::InvalidResponse(
+                "--client-cert and --client-key must be used together".to_string(),
+            ));
+        }
+        (None, None) => {}
+    }
+
+    if options.insecure {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    if let Some(secs) = options.timeout {
+        builder = builder.read_timeout(Duration::from_secs(secs));
+    }
+
+    if let Some(secs) = options.connect_timeout {
+        builder = builder.connect_timeout(Duration::from_secs(secs));
+    }
+
+    // Connections are reused across all transfers of a command
+    if let Some(max_idle) = options.pool_max_idle_per_host {
+        builder = builder.pool_max_idle_per_host(max_idle);
+    }
+    if let Some(secs) = options.pool_idle_timeout {
+        builder = builder.pool_idle_timeout(Duration::from_secs(secs));
+    }
+
+    // HTTP/2 is negotiated via ALPN when the server supports it
+    builder = if options.http1_only {
+        builder.http1_only()
+    } else {
+        builder.http2_adaptive_window(true)
+    };
+
+    Ok(builder.build()?)
+}
+
+/// Creates an API client for a known version that uses `http`
+pub fn with_version(base_url: &str, version: ApiVersion, http: &Client) -> Result<CloudreveAPI> {
+    let mut api = CloudreveAPI::with_version(base_url, version)?;
+    match api.inner_mut() {
+        UnifiedClient::V3(client) => client.http_client = http.clone(),
+        UnifiedClient::V4(client) => client.http_client = http.clone(),
+    }
+    Ok(api)
+}
+
+/// Creates an API client that uses `http`, detecting the server version
+///
+/// Probes V4 first and then V3, like `CloudreveAPI::new`, but through the
+/// configured client so the probe honours proxy and TLS settings as well.
+pub async fn connect(base_url: &str, http: &Client) -> Result<CloudreveAPI> {
+    for version in [ApiVersion::V4, ApiVersion::V3] {
+        let api = with_version(base_url, version, http)?;
+        match api.get_server_version().await {
+            Ok(_) => return Ok(api),
+            Err(e) => debug!("{:?} endpoint failed: {}", version, e),
+        }
+    }
+
+    Err(cloudreve_api::Error::InvalidResponse(
+        "Could not detect API version. Neither V3 nor V4 endpoints responded.".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_http_client_options() {
+        let options = NetworkOptions {
+            proxy: Some("socks5://127.0.0.1:1080".to_string()),
+            insecure: true,
+            timeout: Some(30),
+            connect_timeout: Some(5),
+            pool_max_idle_per_host: Some(16),
+            ..Default::default()
+        };
+        assert!(build_http_client(&options).is_ok());
+
+        // 客户端证书必须同时提供私钥
+        let options = NetworkOptions {
+            client_cert: Some("client.pem".to_string()),
+            ..Default::default()
+        };
+        assert!(build_http_client(&options).is_err());
+    }
+}
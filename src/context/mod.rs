@@ -1,5 +1,7 @@
 pub mod client;
+pub mod http;
 pub mod token_manager;
 
 pub use client::{ClientConfig, initialize_client};
+pub use http::NetworkOptions;
 pub use token_manager::TokenManager;
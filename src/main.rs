@@ -8,6 +8,7 @@
 //! - Share link management
 //! - WebDAV account management
 //! - Settings management
+//! - Layered CLI configuration
 //! - Shell completion support
 //!
 //! ## Usage
@@ -21,10 +22,13 @@
 //!
 //! # Upload a file
 //! cloudreve-cli file upload --file ./photo.jpg --path /photos
+//!
+//! # Set a default instance URL
+//! cloudreve-cli config set default_url https://instance.com
 //! ```
 
 use clap::{Parser, Subcommand};
-use cloudreve_api::{CloudreveAPI, Result};
+use cloudreve_api::Result;
 use log::error;
 use std::sync::atomic::{AtomicBool, Ordering};
 
@@ -77,18 +81,58 @@ struct Cli {
     #[clap(short, long)]
     email: Option<String>,
 
-    /// Authentication token
+    /// Authentication token (falls back to CLOUDREVE_TOKEN)
     #[clap(short, long)]
     token: Option<String>,
 
-    /// Log level (trace, debug, info, warn, error)
-    #[clap(long, default_value = "info")]
-    log_level: String,
+    /// Log level (trace, debug, info, warn, error) [default: info]
+    #[clap(long)]
+    log_level: Option<String>,
 
     /// Show full log prefix (timestamp, level, module path)
     #[clap(long)]
     log_prefix: bool,
 
+    /// HTTP(S) or SOCKS5 proxy URL (e.g. http://proxy:3128, socks5://127.0.0.1:1080)
+    #[clap(long, global = true)]
+    proxy: Option<String>,
+
+    /// Extra CA certificate bundle (PEM) to trust
+    #[clap(long, global = true)]
+    ca_cert: Option<String>,
+
+    /// Client certificate (PEM) for mutual TLS
+    #[clap(long, global = true, requires = "client_key")]
+    client_cert: Option<String>,
+
+    /// Private key (PKCS#8 PEM) for the client certificate
+    #[clap(long, global = true, requires = "client_cert")]
+    client_key: Option<String>,
+
+    /// Skip TLS certificate verification (self-signed test instances only)
+    #[clap(long, global = true)]
+    insecure: bool,
+
+    /// Seconds a request may wait for data before it is aborted
+    #[clap(long, global = true)]
+    timeout: Option<u64>,
+
+    /// Connection timeout in seconds
+    #[clap(long, global = true)]
+    connect_timeout: Option<u64>,
+
+    /// Maximum idle pooled connections per host
+    #[clap(long, global = true)]
+    pool_max_idle_per_host: Option<usize>,
+
+    /// Seconds an idle pooled connection is kept alive
+    #[clap(long, global = true)]
+    pool_idle_timeout: Option<u64>,
+
+    /// Disable HTTP/2
+    #[clap(long, global = true)]
+    http1_only: bool,
+
     #[clap(subcommand)]
     command: Commands,
 }
@@ -97,9 +141,21 @@ struct Cli {
 enum Commands {
     /// Authenticate with Cloudreve
     Auth {
-        /// Password
-        #[clap(short, long)]
+        /// Password (visible in shell history, prefer --password-stdin)
+        #[clap(short, long, conflicts_with_all = ["password_stdin", "password_file"])]
         password: Option<String>,
+
+        /// Read the password from the first line of stdin
+        #[clap(long, conflicts_with = "password_file")]
+        password_stdin: bool,
+
+        /// Read the password from a file
+        #[clap(long)]
+        password_file: Option<String>,
+
+        /// TOTP code for accounts with two-factor authentication enabled
+        #[clap(long)]
+        otp: Option<String>,
     },
 
     /// File management
@@ -120,6 +176,12 @@ enum Commands {
         command: commands::share::ShareCommands,
     },
 
+    /// Trash bin management
+    Trash {
+        #[clap(subcommand)]
+        command: commands::trash::TrashCommands,
+    },
+
     /// Settings management
     Settings {
         #[clap(subcommand)]
@@ -144,6 +206,12 @@ enum Commands {
         command: commands::workflow::WorkflowCommands,
     },
 
+    /// CLI configuration management
+    Config {
+        #[clap(subcommand)]
+        command: commands::config::ConfigCommands,
+    },
+
     /// Generate shell completion script
     Completions {
         /// Shell type (bash, zsh, fish, elvish, powershell)
@@ -157,8 +225,14 @@ enum Commands {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Load configuration file
-    let cfg = config::Config::load().unwrap_or_default();
+    // Load layered configuration (defaults < global < local < env)
+    let cfg = match config::Config::load() {
+        Ok(cfg) => cfg,
+        Err(e) => {
+            eprintln!("Failed to load configuration: {}", e);
+            config::Config::default()
+        }
+    };
 
     // Parse command line arguments
     let cli = Cli::parse();
@@ -166,21 +240,51 @@ async fn main() -> Result<()> {
     // Use config values as defaults if not provided via command line
     let url = cli.url.or_else(|| cfg.default_url.clone());
     let email = cli.email.or_else(|| cfg.default_email.clone());
-    let log_level = if cli.log_level == "info" {
-        // Use config log_level if default wasn't overridden
-        cfg.log_level.clone().unwrap_or_else(|| "info".to_string())
-    } else {
-        cli.log_level
-    };
+    let token = cli
+        .token
+        .or_else(|| std::env::var(utils::credentials::TOKEN_ENV).ok())
+        .filter(|t| !t.is_empty());
+    let log_level = cli
+        .log_level
+        .or_else(|| cfg.log_level.clone())
+        .unwrap_or_else(|| "info".to_string());
 
     // Initialize logger with the specified log level and prefix setting
     init_logging_with_level(&log_level, cli.log_prefix);
 
+    // Config commands work offline and never need a client
+    if let Commands::Config { command } = cli.command {
+        return commands::config::handle_config_command(command);
+    }
+
+    // Network options shared by the API, transfer and preview requests
+    let network = context::NetworkOptions {
+        proxy: cli.proxy.or_else(|| cfg.proxy.clone()),
+        ca_cert: cli.ca_cert.or_else(|| cfg.ca_cert.clone()),
+        client_cert: cli.client_cert.or_else(|| cfg.client_cert.clone()),
+        client_key: cli.client_key.or_else(|| cfg.client_key.clone()),
+        insecure: cli.insecure || cfg.insecure.unwrap_or(false),
+        timeout: cli.timeout.or(cfg.timeout),
+        connect_timeout: cli.connect_timeout.or(cfg.connect_timeout),
+        pool_max_idle_per_host: cli.pool_max_idle_per_host.or(cfg.pool_max_idle_per_host),
+        pool_idle_timeout: cli.pool_idle_timeout.or(cfg.pool_idle_timeout),
+        http1_only: cli.http1_only || cfg.http1_only.unwrap_or(false),
+    };
+
+    // Share links from other instances are opened anonymously
+    if let Commands::Share { command } = &cli.command
+        && command.is_public()
+    {
+        let http = context::http::build_http_client(&network)?;
+        return commands::share::handle_public_command(&http, command).await;
+    }
+
     // Unified API client initialization via context module
     let ctx = context::initialize_client(context::ClientConfig {
         url: url.clone(),
         email: email.clone(),
-        token: cli.token.clone(),
+        token,
+        network,
     })
     .await?;
 
@@ -201,7 +305,7 @@ async fn main() -> Result<()> {
                         std::process::exit(1);
                     }
                 };
-                CloudreveAPI::new(url_val).await?
+                context::http::connect(url_val, &ctx.http).await?
             } else {
                 error!("No cached token found. Please authenticate first using the auth command.");
                 std::process::exit(1);
@@ -211,11 +315,23 @@ async fn main() -> Result<()> {
 
     // Command dispatch
     match cli.command {
-        Commands::Auth { password } => {
-            commands::auth::handle_auth(&mut api, &ctx.token_manager, email, url, password).await?;
+        Commands::Auth {
+            password,
+            password_stdin,
+            password_file,
+            otp,
+        } => {
+            let password = utils::credentials::SecretSource {
+                value: password,
+                from_stdin: password_stdin,
+                file: password_file,
+                env_var: Some(utils::credentials::PASSWORD_ENV),
+            };
+            commands::auth::handle_auth(&mut api, &ctx.token_manager, email, url, password, otp)
+                .await?;
         }
         Commands::File { command } => {
-            commands::file::handle_file_command(&api, command).await?;
+            commands::file::handle_file_command(&api, &ctx.http, &cfg, command).await?;
         }
         Commands::User { command } => {
             commands::user::handle_user_command(&api, &ctx.token_manager, command).await?;
@@ -223,6 +339,9 @@ async fn main() -> Result<()> {
         Commands::Share { command } => {
             commands::share::handle_share_command(&api, command).await?;
         }
+        Commands::Trash { command } => {
+            commands::trash::handle_trash_command(&api, command).await?;
+        }
         Commands::Settings { command } => {
             commands::settings::handle_settings_command(&api, command).await?;
         }
@@ -235,6 +354,7 @@ async fn main() -> Result<()> {
         Commands::Workflow { command } => {
             commands::workflow::handle_workflow_command(&api, command).await?;
         }
+        Commands::Config { .. } => unreachable!("config commands are handled before login"),
         Commands::Completions { shell } => {
             generate_completions(&shell);
             return Ok(());
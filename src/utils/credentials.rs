@@ -0,0 +1,163 @@
+//! Non-interactive credential input
+//!
+//! Secrets can come from a command-line flag, the first line of stdin,
+//! a file, or an environment variable. Interactive prompts are only shown
+//! when stdin is a terminal, so scripts never hang waiting for input.
+
+use cloudreve_api::Result;
+use rpassword::read_password;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// Environment variable holding the account password
+pub const PASSWORD_ENV: &str = "CLOUDREVE_PASSWORD";
+
+/// Environment variable holding an access token
+pub const TOKEN_ENV: &str = "CLOUDREVE_TOKEN";
+
+/// Candidate sources for a single secret, checked in field order
+#[derive(Debug, Default)]
+pub struct SecretSource<'a> {
+    /// Value passed directly on the command line
+    pub value: Option<String>,
+    /// Read the secret from the first line of stdin
+    pub from_stdin: bool,
+    /// Read the secret from a file
+    pub file: Option<String>,
+    /// Environment variable to fall back to
+    pub env_var: Option<&'a str>,
+}
+
+/// Resolves a secret without prompting, returns `None` if no source is set
+pub fn resolve_secret(source: SecretSource) -> Result<Option<String>> {
+    if let Some(value) = source.value {
+        return Ok(Some(value));
+    }
+
+    if source.from_stdin {
+        return read_stdin_line().map(Some);
+    }
+
+    if let Some(path) = source.file {
+        return read_secret_file(&path).map(Some);
+    }
+
+    if let Some(var) = source.env_var
+        && let Ok(value) = std::env::var(var)
+        && !value.is_empty()
+    {
+        return Ok(Some(value));
+    }
+
+    Ok(None)
+}
+
+/// Resolves a required secret, prompting on a terminal as a last resort
+pub fn require_secret(source: SecretSource, prompt: &str) -> Result<String> {
+    if let Some(secret) = resolve_secret(source)? {
+        return Ok(secret);
+    }
+
+    prompt_secret(prompt)?.ok_or_else(|| {
+        cloudreve_api::Error::InvalidResponse(format!(
+            "No password provided and stdin is not a terminal. Use --password-stdin, --password-file or the {} environment variable",
+            PASSWORD_ENV
+        ))
+    })
+}
+
+/// Prompts for a secret with hidden input, returns `None` when stdin is not a TTY
+pub fn prompt_secret(prompt: &str) -> Result<Option<String>> {
+    if !io::stdin().is_terminal() {
+        return Ok(None);
+    }
+
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let input = read_password()?;
+    Ok(Some(input.trim_end_matches(['\r', '\n']).to_string()))
+}
+
+/// Gets a TOTP code from --otp, a terminal prompt, or the next stdin line
+pub fn resolve_otp(otp: Option<String>) -> Result<String> {
+    let code = match otp {
+        Some(code) => code,
+        None if io::stdin().is_terminal() => {
+            print!("Enter 2FA code: ");
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            input
+        }
+        None => read_stdin_line()?,
+    };
+
+    let code = code.trim().to_string();
+    if code.is_empty() || !code.chars().all(|c| c.is_ascii_digit()) {
+        return Err(cloudreve_api::Error::InvalidResponse(
+            "2FA code must be numeric, e.g. 123456".to_string(),
+        ));
+    }
+
+    Ok(code)
+}
+
+/// Reads one line from stdin, stripping the trailing newline
+pub fn read_stdin_line() -> Result<String> {
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Reads a secret from a file, using only its first line
+pub fn read_secret_file(path: &str) -> Result<String> {
+    let content = fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches('\r')
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_secret_file_uses_first_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secret");
+        fs::write(&path, "s3cret pass\r\nignored\n").unwrap();
+
+        let secret = read_secret_file(path.to_str().unwrap()).unwrap();
+        assert_eq!(secret, "s3cret pass");
+    }
+
+    #[test]
+    fn test_resolve_secret_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secret");
+        fs::write(&path, "from-file\n").unwrap();
+
+        // 命令行参数优先于文件
+        let secret = resolve_secret(SecretSource {
+            value: Some("from-flag".to_string()),
+            file: Some(path.to_string_lossy().to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(secret.as_deref(), Some("from-flag"));
+
+        let secret = resolve_secret(SecretSource {
+            file: Some(path.to_string_lossy().to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(secret.as_deref(), Some("from-file"));
+
+        // 没有任何来源时返回 None
+        let secret = resolve_secret(SecretSource::default()).unwrap();
+        assert!(secret.is_none());
+    }
+}
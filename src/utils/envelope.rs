@@ -0,0 +1,33 @@
+//! Raw endpoint responses
+//!
+//! The generic `get`/`post`/`put`/`patch`/`delete` client methods return the
+//! whole `{code, msg, data}` envelope and only fail on HTTP errors. Calls to
+//! endpoints the API crate does not wrap go through [`data`] or [`check`] so
+//! a non-zero code surfaces as an error.
+
+use cloudreve_api::{ApiResponse, Error, Result};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Returns the payload of a successful response
+pub fn data<T: DeserializeOwned>(response: ApiResponse<Value>) -> Result<T> {
+    check_code(&response)?;
+    Ok(serde_json::from_value(
+        response.data.unwrap_or(Value::Null),
+    )?)
+}
+
+/// Checks a response whose payload is not needed
+pub fn check(response: ApiResponse<Value>) -> Result<()> {
+    check_code(&response)
+}
+
+fn check_code(response: &ApiResponse<Value>) -> Result<()> {
+    match response.code {
+        0 => Ok(()),
+        code => Err(Error::Api {
+            code,
+            message: response.msg.clone(),
+        }),
+    }
+}
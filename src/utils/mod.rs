@@ -1,6 +1,12 @@
 // Utility modules
 pub mod concurrency;
+pub mod credentials;
+pub mod envelope;
 pub mod glob;
+pub mod qr;
+pub mod remote;
+pub mod time;
+pub mod walk;
 
 /// Utility functions for CLI display formatting
 /// Format bytes into human-readable size (KB, MB, GB, TB)
@@ -22,3 +28,25 @@ pub fn format_bytes(bytes: i64) -> String {
         format!("{} B", bytes)
     }
 }
+
+/// Quotes a CSV field when it contains a comma, quote or newline
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field() {
+        // 含逗号和引号的字段需要转义
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("plain"), "plain");
+    }
+}
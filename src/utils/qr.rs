@@ -0,0 +1,76 @@
+//! QR codes for share links and WebDAV accounts
+//!
+//! Codes are printed with Unicode half blocks, two modules per character
+//! row, and can also be written to PNG or SVG files.
+
+use cloudreve_api::{Error, Result};
+use log::info;
+use qrcode::QrCode;
+use qrcode::render::{svg, unicode};
+use std::fs;
+
+/// QR output flags shared by the commands that print links
+#[derive(clap::Args, Clone, Debug, Default)]
+pub struct QrOptions {
+    /// Show the link as a QR code in the terminal
+    #[clap(long)]
+    pub qr: bool,
+
+    /// Also write the QR code to a file (.png or .svg)
+    #[clap(long)]
+    pub qr_file: Option<String>,
+}
+
+impl QrOptions {
+    /// Whether any QR output was requested
+    pub fn is_enabled(&self) -> bool {
+        self.qr || self.qr_file.is_some()
+    }
+
+    /// Prints and/or saves `text` as requested
+    pub fn show(&self, text: &str) -> Result<()> {
+        if self.qr {
+            print_qr(text)?;
+        }
+        if let Some(path) = &self.qr_file {
+            save_qr(text, path)?;
+            info!("QR code written to {}", path);
+        }
+        Ok(())
+    }
+}
+
+fn encode(text: &str) -> Result<QrCode> {
+    QrCode::new(text.as_bytes())
+        .map_err(|e| Error::InvalidResponse(format!("Cannot encode QR code: {}", e)))
+}
+
+/// Prints a QR code to stdout, light on dark so it scans on dark terminals
+pub fn print_qr(text: &str) -> Result<()> {
+    let code = encode(text)?;
+    let rendered = code
+        .render::<unicode::Dense1x2>()
+        .dark_color(unicode::Dense1x2::Light)
+        .light_color(unicode::Dense1x2::Dark)
+        .build();
+    println!("{}", rendered);
+    Ok(())
+}
+
+/// Writes a QR code as SVG when the path ends in `.svg`, PNG otherwise
+pub fn save_qr(text: &str, path: &str) -> Result<()> {
+    let code = encode(text)?;
+
+    if path.to_lowercase().ends_with(".svg") {
+        let svg = code.render::<svg::Color>().min_dimensions(256, 256).build();
+        fs::write(path, svg)?;
+    } else {
+        code.render::<image::Luma<u8>>()
+            .min_dimensions(256, 256)
+            .build()
+            .save(path)
+            .map_err(|e| Error::InvalidResponse(format!("Cannot write {}: {}", path, e)))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,234 @@
+//! Raw V4 file listing
+//!
+//! `FileListAll` only exposes name, type and size. Commands that need
+//! timestamps, metadata or URIs list through the `/file` endpoint directly
+//! and deserialize the full file objects.
+
+use crate::utils::envelope;
+use cloudreve_api::api::v4::uri::{path_to_uri, uri_to_path};
+use cloudreve_api::{CloudreveClient, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// URI of the current user's trash bin
+pub const TRASH_URI: &str = "cloudreve://trash";
+
+/// Metadata key holding the original location of a trashed file
+pub const RESTORE_URI_KEY: &str = "sys:restore_uri";
+
+/// Metadata key holding the time a trashed file is purged automatically
+pub const EXPECTED_COLLECT_KEY: &str = "sys:expected_collect_time";
+
+/// File object as returned by the V4 `/file` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteFile {
+    /// 0 = file, 1 = folder
+    #[serde(rename = "type")]
+    pub file_type: i32,
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub size: i64,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub updated_at: String,
+    /// Full `cloudreve://` URI
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    pub metadata: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub owned: bool,
+    #[serde(default)]
+    pub primary_entity: Option<String>,
+}
+
+impl RemoteFile {
+    pub fn is_folder(&self) -> bool {
+        self.file_type == 1
+    }
+
+    /// Gets a metadata value
+    pub fn meta(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get(key))
+            .map(|v| v.as_str())
+    }
+
+    /// Path relative to the user's root, falling back to the raw URI
+    pub fn display_path(&self) -> String {
+        uri_to_path(&self.path).map_or_else(|_| self.path.clone(), str::to_string)
+    }
+}
+
+/// Blob (version, thumbnail, live photo) backing a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteEntity {
+    pub id: String,
+    /// 0 = version, 1 = thumbnail, 2 = live photo
+    #[serde(rename = "type", default)]
+    pub entity_type: i32,
+    #[serde(default)]
+    pub size: i64,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub storage_policy: Option<Value>,
+    #[serde(default)]
+    pub created_by: Option<Value>,
+}
+
+impl RemoteEntity {
+    pub fn is_version(&self) -> bool {
+        self.entity_type == 0
+    }
+
+    /// Name of the storage policy holding this blob
+    pub fn policy_name(&self) -> Option<&str> {
+        self.storage_policy.as_ref()?.get("name")?.as_str()
+    }
+
+    /// Nickname of the uploader
+    pub fn creator_name(&self) -> Option<&str> {
+        self.created_by.as_ref()?.get("nickname")?.as_str()
+    }
+}
+
+/// Extended file information returned with `extended=true`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtendedInfo {
+    #[serde(default)]
+    pub storage_policy: Option<Value>,
+    #[serde(default)]
+    pub storage_policy_inherited: bool,
+    #[serde(default)]
+    pub storage_used: i64,
+    #[serde(default)]
+    pub entities: Vec<RemoteEntity>,
+    #[serde(default)]
+    pub permissions: Option<Value>,
+    #[serde(default)]
+    pub shares: Vec<Value>,
+    #[serde(default)]
+    pub direct_links: Vec<Value>,
+}
+
+impl ExtendedInfo {
+    /// Name of the storage policy the file is placed on
+    pub fn policy_name(&self) -> Option<&str> {
+        self.storage_policy.as_ref()?.get("name")?.as_str()
+    }
+
+    /// Whether a thumbnail blob has been generated
+    pub fn has_thumbnail(&self) -> bool {
+        self.entities.iter().any(|e| e.entity_type == 1)
+    }
+}
+
+/// File object together with its extended information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteFileDetails {
+    #[serde(flatten)]
+    pub file: RemoteFile,
+    #[serde(default)]
+    pub extended_info: Option<ExtendedInfo>,
+}
+
+/// One page of a listing
+pub struct RemotePage {
+    pub files: Vec<RemoteFile>,
+    pub next_token: Option<String>,
+    /// Storage policy of the listed folder
+    pub policy: Option<String>,
+}
+
+/// Converts a path to a URI, leaving `cloudreve://` URIs untouched
+pub fn to_uri(path: &str) -> String {
+    if path.starts_with("cloudreve://") {
+        path.to_string()
+    } else {
+        path_to_uri(path)
+    }
+}
+
+/// Lists one page of a folder by path or URI
+pub async fn list_page(
+    client: &CloudreveClient,
+    path: &str,
+    page_size: u32,
+    next_token: Option<&str>,
+) -> Result<RemotePage> {
+    // The serializer is not Send, so it must be dropped before awaiting
+    let query = {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        query.append_pair("uri", &to_uri(path));
+        query.append_pair("page", "0");
+        query.append_pair("page_size", &page_size.to_string());
+        if let Some(token) = next_token {
+            query.append_pair("next_page_token", token);
+        }
+        query.finish()
+    };
+
+    let response: Value = envelope::data(client.get(&format!("/file?{}", query)).await?)?;
+
+    let files: Vec<RemoteFile> = serde_json::from_value(
+        response
+            .get("files")
+            .cloned()
+            .unwrap_or_else(|| Value::Array(Vec::new())),
+    )?;
+    let next_token = response
+        .pointer("/pagination/next_token")
+        .and_then(|v| v.as_str())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_string());
+    let policy = response
+        .pointer("/storage_policy/name")
+        .and_then(|v| v.as_str())
+        .map(|p| p.to_string());
+
+    Ok(RemotePage {
+        files,
+        next_token,
+        policy,
+    })
+}
+
+/// Lists every item of a folder, following pagination
+pub async fn list_all(
+    client: &CloudreveClient,
+    path: &str,
+    page_size: u32,
+) -> Result<Vec<RemoteFile>> {
+    let mut files = Vec::new();
+    let mut next_token: Option<String> = None;
+
+    loop {
+        let page = list_page(client, path, page_size, next_token.as_deref()).await?;
+        files.extend(page.files);
+
+        match page.next_token {
+            Some(token) => next_token = Some(token),
+            None => break,
+        }
+    }
+
+    Ok(files)
+}
+
+/// Gets a file with its extended information (entities, storage policy)
+pub async fn get_details(client: &CloudreveClient, path: &str) -> Result<RemoteFileDetails> {
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    query.append_pair("uri", &to_uri(path));
+    query.append_pair("extended", "true");
+
+    envelope::data(
+        client
+            .get(&format!("/file/info?{}", query.finish()))
+            .await?,
+    )
+}
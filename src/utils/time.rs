@@ -0,0 +1,87 @@
+//! Duration and date parsing for filters such as `--older-than 30d`
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+/// Parses a duration like `90s`, `15m`, `12h`, `30d` or `2w`
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+
+    let value: i64 = number
+        .parse()
+        .map_err(|_| format!("Invalid duration: {} (expected e.g. 30d, 12h)", input))?;
+
+    match unit {
+        "s" => Ok(Duration::seconds(value)),
+        "m" => Ok(Duration::minutes(value)),
+        "h" => Ok(Duration::hours(value)),
+        "d" | "" => Ok(Duration::days(value)),
+        "w" => Ok(Duration::weeks(value)),
+        _ => Err(format!(
+            "Invalid duration unit in {} (use s, m, h, d or w)",
+            input
+        )),
+    }
+}
+
+/// Parses a point in time: RFC 3339, `YYYY-MM-DD`, or a duration ago (`7d`)
+pub fn parse_time_spec(input: &str) -> Result<DateTime<Utc>, String> {
+    let input = input.trim();
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        && let Some(dt) = date.and_hms_opt(0, 0, 0)
+    {
+        return Ok(dt.and_utc());
+    }
+
+    parse_duration(input).map(|d| Utc::now() - d).map_err(|_| {
+        format!(
+            "Invalid time: {} (use YYYY-MM-DD, RFC 3339, or an age like 7d)",
+            input
+        )
+    })
+}
+
+/// Parses a server timestamp, `None` if it is empty or malformed
+pub fn parse_remote_time(time_str: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(time_str)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30d").unwrap(), Duration::days(30));
+        assert_eq!(parse_duration("12h").unwrap(), Duration::hours(12));
+        assert_eq!(parse_duration("2w").unwrap(), Duration::weeks(2));
+        assert_eq!(parse_duration("7").unwrap(), Duration::days(7));
+        assert!(parse_duration("d").is_err());
+        assert!(parse_duration("5y").is_err());
+    }
+
+    #[test]
+    fn test_parse_time_spec() {
+        let dt = parse_time_spec("2024-03-01").unwrap();
+        assert_eq!(dt.to_rfc3339(), "2024-03-01T00:00:00+00:00");
+
+        let dt = parse_time_spec("2024-03-01T12:00:00+08:00").unwrap();
+        assert_eq!(dt.to_rfc3339(), "2024-03-01T04:00:00+00:00");
+
+        // 相对时间：距今 1 天
+        let dt = parse_time_spec("1d").unwrap();
+        assert!(Utc::now() - dt >= Duration::days(1));
+
+        assert!(parse_time_spec("yesterday").is_err());
+    }
+}
@@ -0,0 +1,183 @@
+//! 远程目录递归遍历
+//!
+//! 按层级广度优先遍历，同一层的目录并发列出（每个目录内部自动分页）。
+
+use crate::utils::concurrency::execute_with_concurrency;
+use crate::utils::{remote, time};
+use chrono::{DateTime, Utc};
+use cloudreve_api::{CloudreveAPI, FileListAll, Result, UnifiedClient};
+use log::warn;
+
+/// 遍历得到的远程条目
+#[derive(Debug, Clone)]
+pub struct RemoteNode {
+    /// 完整路径，如 `/docs/a.txt`
+    pub path: String,
+    pub name: String,
+    pub is_folder: bool,
+    pub size: i64,
+    /// 相对根目录的深度，根目录的直接子项为 1
+    pub depth: usize,
+    /// 创建时间，无法解析时为 `None`
+    pub created_at: Option<DateTime<Utc>>,
+    /// 最后修改时间（仅 V4 提供）
+    pub updated_at: Option<String>,
+    /// 所在目录的存储策略名称（仅 V4 提供）
+    pub policy: Option<String>,
+}
+
+impl RemoteNode {
+    /// 父目录路径
+    pub fn parent(&self) -> &str {
+        match self.path.rfind('/') {
+            Some(0) | None => "/",
+            Some(pos) => &self.path[..pos],
+        }
+    }
+}
+
+/// 拼接目录和名称
+pub fn join_path(dir: &str, name: &str) -> String {
+    let dir = dir.trim_end_matches('/');
+    format!("{}/{}", dir, name)
+}
+
+/// 规范化远程路径：保证以 `/` 开头且不以 `/` 结尾（根目录除外）
+pub fn normalize_path(path: &str) -> String {
+    let trimmed = path.trim_start_matches("cloudreve://my").trim_matches('/');
+    if trimmed.is_empty() {
+        "/".to_string()
+    } else {
+        format!("/{}", trimmed)
+    }
+}
+
+/// 递归列出 `root` 下的所有条目
+///
+/// - `max_depth`: 最大遍历深度，`None` 表示不限制
+/// - `concurrency`: 同时列出的目录数
+///
+/// 无法列出的子目录会记录警告并跳过，根目录失败则返回错误。
+pub async fn walk(
+    api: &CloudreveAPI,
+    root: &str,
+    max_depth: Option<usize>,
+    concurrency: usize,
+) -> Result<Vec<RemoteNode>> {
+    let root = normalize_path(root);
+    let mut nodes = list_folder(api, &root, 1).await?;
+
+    let mut frontier: Vec<String> = nodes
+        .iter()
+        .filter(|n| n.is_folder)
+        .map(|n| n.path.clone())
+        .collect();
+    let mut depth = 1;
+
+    while !frontier.is_empty() && max_depth.is_none_or(|max| depth < max) {
+        depth += 1;
+
+        let tasks: Vec<_> = frontier
+            .drain(..)
+            .map(|dir| {
+                let api = api.clone();
+                (
+                    dir.clone(),
+                    async move { list_folder(&api, &dir, depth).await },
+                )
+            })
+            .collect();
+
+        for (dir, result) in execute_with_concurrency(tasks, concurrency).await {
+            match result {
+                Ok(children) => {
+                    frontier.extend(
+                        children
+                            .iter()
+                            .filter(|n| n.is_folder)
+                            .map(|n| n.path.clone()),
+                    );
+                    nodes.extend(children);
+                }
+                Err(e) => warn!("Failed to list {}: {}", dir, e),
+            }
+        }
+    }
+
+    Ok(nodes)
+}
+
+/// 列出单个目录的全部条目（自动分页）
+///
+/// V4 通过 `/file` 接口列出，以获得修改时间和存储策略。
+async fn list_folder(api: &CloudreveAPI, dir: &str, depth: usize) -> Result<Vec<RemoteNode>> {
+    if let UnifiedClient::V4(client) = api.inner() {
+        let mut page = remote::list_page(client, dir, 100, None).await?;
+        let policy = page.policy.take();
+        let mut files = page.files;
+        while let Some(token) = page.next_token {
+            page = remote::list_page(client, dir, 100, Some(&token)).await?;
+            files.append(&mut page.files);
+        }
+
+        return Ok(files
+            .into_iter()
+            .map(|file| RemoteNode {
+                path: join_path(dir, &file.name),
+                is_folder: file.is_folder(),
+                size: file.size,
+                depth,
+                created_at: time::parse_remote_time(&file.created_at),
+                updated_at: Some(file.updated_at),
+                policy: policy.clone(),
+                name: file.name,
+            })
+            .collect());
+    }
+
+    let FileListAll::V3(list) = api.list_files_all(dir, None).await? else {
+        unreachable!("V4 folders are listed above");
+    };
+
+    Ok(list
+        .objects
+        .into_iter()
+        .map(|object| RemoteNode {
+            path: join_path(dir, &object.name),
+            is_folder: object.object_type == "dir",
+            size: object.size,
+            depth,
+            created_at: time::parse_remote_time(&object.create_date),
+            updated_at: None,
+            policy: None,
+            name: object.name,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_and_parent() {
+        assert_eq!(normalize_path("/"), "/");
+        assert_eq!(normalize_path("docs/"), "/docs");
+        assert_eq!(normalize_path("cloudreve://my/docs/a"), "/docs/a");
+
+        // 根目录下条目的父目录是 /
+        let node = RemoteNode {
+            path: join_path("/", "a.txt"),
+            name: "a.txt".to_string(),
+            is_folder: false,
+            size: 0,
+            depth: 1,
+            created_at: None,
+            updated_at: None,
+            policy: None,
+        };
+        assert_eq!(node.path, "/a.txt");
+        assert_eq!(node.parent(), "/");
+        assert_eq!(join_path("/docs/", "b"), "/docs/b");
+    }
+}
@@ -29,6 +29,7 @@ pub async fn run_v4_auth_tests(config: &EnvironmentConfig) -> CliTestResults {
     println!("╚══════════════════════════════════════════════════════════╝");
 
     results.add_test_tuple(test_auth(config, "V4").await);
+    results.add_test_tuple(test_auth_password_stdin(config, "V4").await);
 
     results.duration_ms = start.elapsed().as_millis() as u64;
     results
@@ -71,3 +72,41 @@ async fn test_auth(
         )
     }
 }
+
+async fn test_auth_password_stdin(
+    config: &EnvironmentConfig,
+    version: &str,
+) -> (String, String, String, Option<i32>, String) {
+    println!("  [{}] 测试 stdin 密码认证...", version);
+
+    let runner = CliRunner::new()
+        .with_base_url(config.base_url.clone())
+        .with_email(config.username.clone())
+        .with_timeout(Duration::from_secs(30));
+
+    // 密码通过 stdin 传递，不出现在命令行参数中
+    let result = runner.run_with_input(
+        &["auth", "--password-stdin"],
+        &format!("{}\n", config.password),
+    );
+
+    if result.success {
+        println!("  [{}] ✓ stdin 密码认证成功", version);
+        (
+            "auth".to_string(),
+            "--password-stdin".to_string(),
+            version.to_string(),
+            result.exit_code,
+            String::new(),
+        )
+    } else {
+        println!("  [{}] ✗ stdin 密码认证失败: {}", version, result.stderr);
+        (
+            "auth".to_string(),
+            "--password-stdin".to_string(),
+            version.to_string(),
+            result.exit_code,
+            result.stderr.clone(),
+        )
+    }
+}
@@ -192,7 +192,7 @@ async fn test_file_mkdir(
     let result = runner.run(&["file", "mkdir", "--path", &test_dir]);
 
     if result.success {
-        let _ = runner.run(&["file", "delete", "--path", &test_dir, "--force"]);
+        let _ = runner.run(&["file", "delete", "--path", &test_dir, "--force", "-r"]);
         println!("  [File] ✓ mkdir 命令成功");
         (
             "file mkdir".to_string(),
@@ -429,7 +429,7 @@ async fn test_file_move(
     ]);
 
     if result.success {
-        let _ = runner.run(&["file", "delete", "--path", &test_dir, "--force"]);
+        let _ = runner.run(&["file", "delete", "--path", &test_dir, "--force", "-r"]);
         println!("  [File] ✓ move 命令成功");
         (
             "file move".to_string(),
@@ -542,7 +542,7 @@ async fn test_file_copy(
             &format!("/{}", filename),
             "--force",
         ]);
-        let _ = runner.run(&["file", "delete", "--path", &test_dir, "--force"]);
+        let _ = runner.run(&["file", "delete", "--path", &test_dir, "--force", "-r"]);
         println!("  [File] ✓ copy 命令成功（已验证文件被复制）");
         (
             "file copy".to_string(),
@@ -560,7 +560,7 @@ async fn test_file_copy(
             &format!("/{}", filename),
             "--force",
         ]);
-        let _ = runner.run(&["file", "delete", "--path", &test_dir, "--force"]);
+        let _ = runner.run(&["file", "delete", "--path", &test_dir, "--force", "-r"]);
         println!(
             "  [File] ✗ copy 命令失败: {} (验证: {})",
             result.stderr,
@@ -749,7 +749,7 @@ async fn test_file_sync(
         "up",
         "--dry-run",
     ]);
-    let _ = runner.run(&["file", "delete", "--path", &remote_dir, "--force"]);
+    let _ = runner.run(&["file", "delete", "--path", &remote_dir, "--force", "-r"]);
 
     if result.stderr.contains("not yet supported") {
         println!("  [File] ⊘ sync 命令不支持");